@@ -0,0 +1,160 @@
+use serde::Serialize;
+
+use super::winning_tiles;
+use crate::{
+    mentsu::Mentsu,
+    parser::{Hand, WinMethod},
+    tile::Tile,
+    win_context::WinContext,
+};
+
+/// The tiles a player has discarded or let pass, for furiten checks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pond {
+    /// Every tile the player has discarded, including ones others called.
+    pub discards: Vec<Tile>,
+    /// Tiles others discarded since the player's last discard that the
+    /// player didn't ron.
+    pub missed: Vec<Tile>,
+    /// Tiles others discarded since the player declared riichi that the
+    /// player didn't ron.
+    pub missed_since_riichi: Vec<Tile>,
+}
+
+/// A reason the player can't win by ron. Each holds the wait that causes it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+pub enum Furiten {
+    /// A wait is among the player's own discards. Lasts until the waits
+    /// change.
+    Permanent(Tile),
+    /// A wait was passed since the player's last discard. Lasts until they
+    /// discard again.
+    Temporary(Tile),
+    /// A wait was passed after riichi. Lasts for the rest of the hand.
+    Riichi(Tile),
+}
+
+impl std::fmt::Display for Furiten {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Permanent(t) => write!(f, "permanent furiten: {t} was discarded"),
+            Self::Temporary(t) => write!(f, "temporary furiten: {t} was passed this go-around"),
+            Self::Riichi(t) => write!(f, "riichi furiten: {t} was passed after riichi"),
+        }
+    }
+}
+
+/// Checks a tenpai hand for furiten, one entry per wait and kind of
+/// furiten. Empty means the hand may ron.
+///
+/// `tiles` are the concealed tiles before the win: 13 less three for each
+/// called meld. Riichi furiten is only checked if `ctx` has riichi.
+pub fn furiten(tiles: &[Tile], melds: &[Mentsu], pond: &Pond, ctx: WinContext) -> Vec<Furiten> {
+    let mut found = Vec::new();
+
+    for wait in winning_tiles(tiles, melds) {
+        if pond.discards.contains(&wait) {
+            found.push(Furiten::Permanent(wait));
+        }
+
+        if pond.missed.contains(&wait) {
+            found.push(Furiten::Temporary(wait));
+        }
+
+        if ctx.any_riichi() && pond.missed_since_riichi.contains(&wait) {
+            found.push(Furiten::Riichi(wait));
+        }
+    }
+
+    found
+}
+
+/// Checks a won hand for furiten. Always empty for tsumo, which furiten
+/// doesn't prevent.
+pub fn ron_furiten(hand: &Hand, pond: &Pond, ctx: WinContext) -> Vec<Furiten> {
+    if hand.win_method == WinMethod::Tsumo {
+        return Vec::new();
+    }
+
+    let mut tiles = hand.tiles.clone();
+    if let Some(i) = tiles.iter().position(|&t| t == hand.win_tile) {
+        tiles.remove(i);
+    }
+
+    furiten(&tiles, &hand.melds, pond, ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn tiles(s: &str) -> Vec<Tile> {
+        parser::parse_tiles(s).unwrap()
+    }
+
+    /// Waits on 1s and 4s.
+    const HAND: &str = "1123s456m789p333z";
+
+    fn check(pond: &Pond, ctx: WinContext) -> Vec<Furiten> {
+        furiten(&tiles(HAND), &[], pond, ctx)
+    }
+
+    #[test]
+    fn permanent() {
+        let pond = Pond {
+            discards: tiles("9m4s"),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            check(&pond, WinContext::default()),
+            [Furiten::Permanent(tiles("4s")[0])]
+        );
+        assert!(check(&Pond::default(), WinContext::default()).is_empty());
+    }
+
+    #[test]
+    fn temporary() {
+        let pond = Pond {
+            missed: tiles("1s"),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            check(&pond, WinContext::default()),
+            [Furiten::Temporary(tiles("1s")[0])]
+        );
+    }
+
+    #[test]
+    fn riichi() {
+        let pond = Pond {
+            missed_since_riichi: tiles("4s5s"),
+            ..Default::default()
+        };
+        let riichi = WinContext {
+            riichi: true,
+            ..Default::default()
+        };
+
+        assert_eq!(check(&pond, riichi), [Furiten::Riichi(tiles("4s")[0])]);
+        assert!(check(&pond, WinContext::default()).is_empty());
+    }
+
+    #[test]
+    fn only_ron_is_blocked() {
+        let pond = Pond {
+            discards: tiles("4s"),
+            ..Default::default()
+        };
+        let ron = parser::parse("1123s456m789p333z 4s").unwrap();
+        let tsumo = parser::parse("1123s456m789p333z4s").unwrap();
+
+        assert_eq!(
+            ron_furiten(&ron, &pond, WinContext::default()),
+            [Furiten::Permanent(tiles("4s")[0])]
+        );
+        assert!(ron_furiten(&tsumo, &pond, WinContext::default()).is_empty());
+    }
+}
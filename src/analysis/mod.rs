@@ -0,0 +1,27 @@
+//! Hand efficiency: how far a hand is from winning, what it waits on,
+//! and whether it may ron.
+
+use crate::tile::{KINDS, Tile};
+
+mod furiten;
+mod shanten;
+mod ukeire;
+mod waits;
+
+pub use furiten::{Furiten, Pond, furiten, ron_furiten};
+pub use shanten::{chiitoitsu_shanten, kokushi_shanten, shanten, standard_shanten};
+pub use ukeire::{Discard, ukeire};
+pub use waits::{Wait, waits, winning_tiles};
+
+/// Copies of each distinct tile, indexed by [`Tile::index`].
+pub(crate) type Counts = [u8; KINDS];
+
+pub(crate) fn counts(tiles: &[Tile]) -> Counts {
+    let mut counts = [0; KINDS];
+
+    for t in tiles {
+        counts[t.index()] += 1;
+    }
+
+    counts
+}
@@ -0,0 +1,389 @@
+use super::{Counts, counts};
+use crate::{
+    mentsu::{self, Mentsu},
+    tile::{KINDS, Tile},
+};
+
+/// Returns how many tiles the hand is from tenpai: 0 is tenpai and -1 is a
+/// complete hand.
+///
+/// `tiles` are the concealed tiles: 13 or 14, less three for each called
+/// meld in `melds`. Chiitoitsu and kokushi are only considered for hands
+/// without calls.
+///
+/// Shapes that can only be completed by a fifth copy of a tile don't count,
+/// so a hand whose every wait is held four times isn't tenpai.
+pub fn shanten(tiles: &[Tile], melds: &[Mentsu]) -> i32 {
+    shanten_counts(&counts(tiles), &called(melds), melds.len())
+}
+
+/// Shanten for four sets and a pair only.
+pub fn standard_shanten(tiles: &[Tile], melds: &[Mentsu]) -> i32 {
+    standard(&counts(tiles), &called(melds), melds.len())
+}
+
+/// Shanten for seven distinct pairs only.
+pub fn chiitoitsu_shanten(tiles: &[Tile]) -> i32 {
+    chiitoitsu(&counts(tiles))
+}
+
+/// Shanten for thirteen orphans only.
+pub fn kokushi_shanten(tiles: &[Tile]) -> i32 {
+    kokushi(&counts(tiles))
+}
+
+/// Copies of each tile in the called melds.
+pub(crate) fn called(melds: &[Mentsu]) -> Counts {
+    counts(&mentsu::get_tiles(melds))
+}
+
+/// Shanten of the concealed `counts`, alongside `melds` called melds whose
+/// tiles are counted in `called`.
+pub(crate) fn shanten_counts(counts: &Counts, called: &Counts, melds: usize) -> i32 {
+    let standard = standard(counts, called, melds);
+
+    if melds > 0 {
+        return standard;
+    }
+
+    standard.min(chiitoitsu(counts)).min(kokushi(counts))
+}
+
+fn standard(counts: &Counts, called: &Counts, melds: usize) -> i32 {
+    let mut full = *counts;
+    for (n, c) in full.iter_mut().zip(called) {
+        *n += c;
+    }
+
+    let shapes = [
+        block(&counts[0..9], &full[0..9], true),
+        block(&counts[9..18], &full[9..18], true),
+        block(&counts[18..27], &full[18..27], true),
+        block(&counts[27..KINDS], &full[27..KINDS], false),
+    ]
+    .into_iter()
+    .fold(Shapes::EMPTY, Shapes::combine);
+
+    shapes
+        .iter()
+        .map(|(sets, partials, pair, float)| {
+            let sets = (sets + melds as u32).min(4);
+            // Only four sets count, complete or not.
+            let partials = partials.min(4 - sets);
+
+            // Four sets wait on a tanki, which needs a tile that's left.
+            let dead_tanki = sets == 4 && !pair && !float;
+
+            8 - 2 * sets as i32 - partials as i32 - i32::from(pair) + i32::from(dead_tanki)
+        })
+        .min()
+        .unwrap_or(8)
+}
+
+/// Every combination of (sets, partial sets, pair, live float) some part of
+/// a hand can be split into, as a bitset. Partial sets are pairs,
+/// ryanmen/penchan and kanchan shapes. A live float is a leftover tile with
+/// copies still to draw, which a tanki can wait on. Sets and partials stop
+/// counting at 4, past which they can't lower shanten.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Shapes(u128);
+
+impl Shapes {
+    /// Nothing but (0, 0, no pair, no float).
+    const EMPTY: Self = Self(1);
+
+    const fn bit(sets: u32, partials: u32, pair: bool, float: bool) -> u128 {
+        1 << ((float as u32) * 50 + (pair as u32) * 25 + sets * 5 + partials)
+    }
+
+    fn iter(self) -> impl Iterator<Item = (u32, u32, bool, bool)> {
+        let mut bits = self.0;
+
+        std::iter::from_fn(move || {
+            let i = bits.trailing_zeros();
+            bits &= bits.checked_sub(1)?;
+
+            Some(((i % 25) / 5, i % 5, i % 50 >= 25, i >= 50))
+        })
+    }
+
+    /// Adds the same shape to every combination.
+    fn add(self, sets: u32, partials: u32, pair: bool, float: bool) -> Self {
+        let mut ans = 0;
+
+        for (s, p, h, f) in self.iter() {
+            if !(pair && h) {
+                ans |= Self::bit(
+                    (s + sets).min(4),
+                    (p + partials).min(4),
+                    pair || h,
+                    float || f,
+                );
+            }
+        }
+
+        Self(ans)
+    }
+
+    /// Combinations for two independent parts of a hand taken together.
+    fn combine(self, other: Self) -> Self {
+        let mut ans = Self(0);
+
+        for (s, p, h, f) in other.iter() {
+            ans.0 |= self.add(s, p, h, f).0;
+        }
+
+        ans
+    }
+}
+
+/// Finds the shapes for one suit. Sequences can't wrap around a suit, so
+/// each suit is split independently. `full` counts the suit's tiles in
+/// called melds too, and partial sets or floats whose every completing
+/// tile is held four times in it don't count.
+///
+/// Unlike `parser::i13s::rec_build`, which lists every decomposition, this
+/// only tracks which shapes are reachable. Once the tiles below `i` are
+/// used up, what's left depends only on `i` and the three counts from `i`
+/// on, so those are memoized.
+fn block(counts: &[u8], full: &[u8], sequences: bool) -> Shapes {
+    // Copies beyond the fourth can't exist, so don't need keys.
+    let count = |i: usize| counts.get(i).map_or(0, |&n| n.min(4) as usize);
+
+    let mut live = [false; 9];
+    for (l, &n) in live.iter_mut().zip(full) {
+        *l = n < 4;
+    }
+
+    let mut memo = [Shapes(0); 9 * 125];
+
+    block_from(
+        &count,
+        &live,
+        sequences,
+        0,
+        [count(0), count(1), count(2)],
+        &mut memo,
+    )
+}
+
+fn block_from(
+    count: &dyn Fn(usize) -> usize,
+    live: &[bool; 9],
+    sequences: bool,
+    i: usize,
+    [x, y, z]: [usize; 3],
+    memo: &mut [Shapes; 9 * 125],
+) -> Shapes {
+    if i == 9 {
+        return Shapes::EMPTY;
+    }
+
+    if x == 0 {
+        return block_from(count, live, sequences, i + 1, [y, z, count(i + 3)], memo);
+    }
+
+    let key = i * 125 + x * 25 + y * 5 + z;
+    if memo[key] != Shapes(0) {
+        return memo[key];
+    }
+
+    let mut ans = Shapes(0);
+    let mut go = |next: [usize; 3], sets, partials, pair, float| {
+        ans.0 |= block_from(count, live, sequences, i, next, memo)
+            .add(sets, partials, pair, float)
+            .0;
+    };
+
+    // Whether the tile at `j` places from here is left to draw.
+    let waits_on = |j: isize| {
+        usize::try_from(i as isize + j)
+            .ok()
+            .and_then(|k| live.get(k))
+            .is_some_and(|&l| l)
+    };
+
+    // Leave one copy unused
+    go([x - 1, y, z], 0, 0, false, waits_on(0));
+
+    if x >= 2 {
+        go([x - 2, y, z], 0, 0, true, false);
+
+        if waits_on(0) {
+            go([x - 2, y, z], 0, 1, false, false);
+        }
+    }
+
+    if x >= 3 {
+        go([x - 3, y, z], 1, 0, false, false);
+    }
+
+    if sequences {
+        if y >= 1 && (waits_on(-1) || waits_on(2)) {
+            go([x - 1, y - 1, z], 0, 1, false, false);
+        }
+
+        if z >= 1 && waits_on(1) {
+            go([x - 1, y, z - 1], 0, 1, false, false);
+        }
+
+        if y >= 1 && z >= 1 {
+            go([x - 1, y - 1, z - 1], 1, 0, false, false);
+        }
+    }
+
+    memo[key] = ans;
+    ans
+}
+
+fn chiitoitsu(counts: &Counts) -> i32 {
+    let pairs = counts.iter().filter(|&&n| n >= 2).count() as i32;
+    let kinds = counts.iter().filter(|&&n| n >= 1).count() as i32;
+
+    // Pairs must be distinct, so too few kinds costs extra draws.
+    6 - pairs + (7 - kinds).max(0)
+}
+
+fn kokushi(counts: &Counts) -> i32 {
+    let orphans = || (0..KINDS).filter(|&i| !Tile::from_index(i).simple());
+
+    let kinds = orphans().filter(|&i| counts[i] >= 1).count() as i32;
+    let pair = orphans().any(|i| counts[i] >= 2);
+
+    13 - kinds - i32::from(pair)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{self, WinMethod};
+
+    fn tiles(s: &str) -> Vec<Tile> {
+        parser::parse_tiles(s).unwrap()
+    }
+
+    #[test]
+    fn fixed_values() {
+        assert_eq!(shanten(&tiles("123m456p789s111z22z"), &[]), -1);
+        assert_eq!(shanten(&tiles("123m456p789s111z2z"), &[]), 0);
+        assert_eq!(shanten(&tiles("123m456p789s11z23z"), &[]), 1);
+        assert_eq!(shanten(&tiles("147m258p369s1234z"), &[]), 6);
+        assert_eq!(shanten(&tiles("1122m3344p5566s7z"), &[]), 0);
+        assert_eq!(chiitoitsu_shanten(&tiles("11223344m55667p")), 0);
+        assert_eq!(shanten(&tiles("19m19p19s1234567z"), &[]), 0);
+        assert_eq!(kokushi_shanten(&tiles("19m19p19s123456z5m")), 1);
+    }
+
+    #[test]
+    fn dead_waits() {
+        // Tanki on a tile held four times.
+        assert_eq!(shanten(&tiles("1111m234p567s789s"), &[]), 1);
+        assert_eq!(standard_shanten(&tiles("1111m234p567s789s"), &[]), 1);
+        assert_eq!(shanten(&tiles("1111m234p567s789s1z"), &[]), 0);
+
+        // The fourth copy is in a called pon.
+        let melds = parser::parse("[111m]1m234p567s789s5z").unwrap().melds;
+        assert_eq!(shanten(&tiles("1m234p567s789s"), &melds), 1);
+
+        // Kanchan on a tile that's all in a called kan.
+        let melds = parser::parse("[2222m]13m567p789s55z5z").unwrap().melds;
+        assert_eq!(shanten(&tiles("13m567p789s55z"), &melds), 1);
+    }
+
+    /// Small xorshift generator, so the tests need no dependencies.
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % n as u64) as usize
+        }
+    }
+
+    /// A complete hand with a few tiles swapped for random ones, so that
+    /// it's likely to be close to tenpai.
+    fn near_complete(rng: &mut Rng, len: usize) -> Vec<Tile> {
+        loop {
+            let mut counts = [0u8; KINDS];
+            counts[rng.below(KINDS)] += 2;
+
+            for _ in 0..4 {
+                let i = rng.below(KINDS);
+                if i < 27 && i % 9 < 7 && rng.below(2) == 0 {
+                    for n in &mut counts[i..i + 3] {
+                        *n += 1;
+                    }
+                } else {
+                    counts[i] += 3;
+                }
+            }
+
+            for _ in 0..rng.below(3) {
+                let i = rng.below(KINDS);
+                let j = rng.below(KINDS);
+                if counts[i] > 0 {
+                    counts[i] -= 1;
+                    counts[j] += 1;
+                }
+            }
+
+            if counts.iter().any(|&n| n > 4) {
+                continue;
+            }
+
+            let mut tiles: Vec<Tile> = Tile::all()
+                .flat_map(|t| std::iter::repeat_n(t, counts[t.index()].into()))
+                .collect();
+
+            while tiles.len() > len {
+                tiles.remove(rng.below(tiles.len()));
+            }
+
+            return tiles;
+        }
+    }
+
+    fn complete(tiles: &[Tile]) -> bool {
+        !parser::interpret(tiles, &[], tiles[0], WinMethod::Tsumo).is_empty()
+    }
+
+    fn tenpai(tiles: &[Tile]) -> bool {
+        let held = counts(tiles);
+
+        Tile::all()
+            .filter(|t| held[t.index()] < 4)
+            .any(|t| complete(&[tiles, &[t]].concat()))
+    }
+
+    #[test]
+    fn matches_brute_force_near_tenpai() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..300 {
+            let hand = near_complete(&mut rng, 13);
+            assert_eq!(
+                shanten(&hand, &[]) == 0,
+                tenpai(&hand),
+                "13 tiles: {hand:?}"
+            );
+        }
+
+        for _ in 0..100 {
+            let hand = near_complete(&mut rng, 14);
+            let shanten = shanten(&hand, &[]);
+
+            assert_eq!(shanten == -1, complete(&hand), "14 tiles: {hand:?}");
+
+            if shanten != -1 {
+                let tenpai_discard = (0..hand.len()).any(|i| {
+                    let mut rest = hand.clone();
+                    rest.remove(i);
+                    tenpai(&rest)
+                });
+
+                assert_eq!(shanten == 0, tenpai_discard, "14 tiles: {hand:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::cmp::Reverse;
+
+use serde::Serialize;
+
+use super::{
+    counts,
+    shanten::{called, shanten_counts},
+};
+use crate::{
+    mentsu::{self, Mentsu},
+    tile::Tile,
+};
+
+/// What's left of a hand after discarding one tile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Discard {
+    pub tile: Tile,
+    /// Shanten of the remaining 13 tiles.
+    pub shanten: i32,
+    /// Tiles that would lower shanten if drawn, each with how many copies
+    /// haven't been seen yet.
+    pub accepts: Vec<(Tile, u32)>,
+    /// Total unseen copies of every tile in `accepts`.
+    pub available: u32,
+}
+
+/// Builds the efficiency table for a 14-tile hand: one row per distinct
+/// discard, best first.
+///
+/// `tiles` are the concealed tiles, including the one just drawn: 14 less
+/// three for each called meld. `visible` are tiles seen elsewhere, such as
+/// discards, other players' calls and dora indicators. The hand's own tiles
+/// are counted as seen.
+///
+/// Rows are sorted by shanten, then by most tiles available, then by tile.
+pub fn ukeire(tiles: &[Tile], melds: &[Mentsu], visible: &[Tile]) -> Vec<Discard> {
+    let held = counts(tiles);
+    let called = called(melds);
+    let seen = counts(&[tiles, &mentsu::get_tiles(melds), visible].concat());
+
+    let mut table: Vec<Discard> = Tile::all()
+        .filter(|t| held[t.index()] > 0)
+        .map(|discard| {
+            let mut after = held;
+            after[discard.index()] -= 1;
+
+            let shanten = shanten_counts(&after, &called, melds.len());
+
+            let accepts: Vec<(Tile, u32)> = Tile::all()
+                .filter_map(|t| {
+                    after[t.index()] += 1;
+                    let improves = shanten_counts(&after, &called, melds.len()) < shanten;
+                    after[t.index()] -= 1;
+
+                    improves.then(|| (t, 4u32.saturating_sub(seen[t.index()].into())))
+                })
+                .collect();
+
+            Discard {
+                tile: discard,
+                shanten,
+                available: accepts.iter().map(|(_, n)| n).sum(),
+                accepts,
+            }
+        })
+        .collect();
+
+    table.sort_by_key(|d| (d.shanten, Reverse(d.available), d.tile.index()));
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parser, tile::Suit};
+
+    #[test]
+    fn dead_tanki_is_not_tenpai() {
+        let table = ukeire(
+            &parser::parse_tiles("1111m234p567s789s1z").unwrap(),
+            &[],
+            &[],
+        );
+        let east = Tile::new(1, Suit::Honor);
+
+        assert_eq!(table[0].tile, Tile::new(1, Suit::Man));
+        assert_eq!(table[0].shanten, 0);
+        assert_eq!(table[0].accepts, [(east, 3)]);
+
+        let row = table.iter().find(|d| d.tile == east).unwrap();
+        assert_eq!(row.shanten, 1);
+        assert!(!row.accepts.is_empty());
+    }
+}
@@ -0,0 +1,180 @@
+use super::{
+    counts,
+    shanten::{called, shanten_counts},
+};
+use crate::{
+    mentsu::Mentsu,
+    parser::{Hand, WinMethod, WinWait},
+    player_state::PlayerState,
+    ruleset::Ruleset,
+    score::{Scores, score_hand},
+    table_state::TableState,
+    tile::Tile,
+    win_context::WinContext,
+};
+
+/// A tile that completes a tenpai hand.
+#[derive(Debug, Clone)]
+pub struct Wait {
+    pub tile: Tile,
+    /// Every wait shape the tile completes, across all interpretations.
+    pub shapes: Vec<WinWait>,
+    /// The hand won by ron on this tile. Empty if `ctx` rules out ron.
+    pub ron: Scores,
+    /// The hand won by tsumo on this tile. Empty if `ctx` rules out tsumo.
+    pub tsumo: Scores,
+}
+
+impl Wait {
+    /// Returns `true` if winning on this tile by `win_method` has a yaku.
+    pub fn has_yaku(&self, win_method: WinMethod) -> bool {
+        match win_method {
+            WinMethod::Ron => self.ron.best().is_some(),
+            WinMethod::Tsumo => self.tsumo.best().is_some(),
+        }
+    }
+}
+
+/// Returns every tile that completes the hand, in tile order.
+///
+/// `tiles` are the concealed tiles: 13 less three for each called meld.
+/// Tiles the hand already holds all four of can't be drawn, so they're
+/// never waits.
+pub fn winning_tiles(tiles: &[Tile], melds: &[Mentsu]) -> Vec<Tile> {
+    let mut held = counts(tiles);
+    let called = called(melds);
+
+    Tile::all()
+        .filter(|t| {
+            if held[t.index()] + called[t.index()] >= 4 {
+                return false;
+            }
+
+            held[t.index()] += 1;
+            let complete = shanten_counts(&held, &called, melds.len()) == -1;
+            held[t.index()] -= 1;
+
+            complete
+        })
+        .collect()
+}
+
+/// Lists the hand's waits, with their shapes and what each is worth by ron
+/// and by tsumo. Returns nothing if the hand isn't tenpai.
+///
+/// Ura-dora aren't known until the hand is won, so they aren't counted.
+pub fn waits(
+    tiles: &[Tile],
+    melds: &[Mentsu],
+    dora: &[Tile],
+    player: PlayerState,
+    table: TableState,
+    ctx: WinContext,
+    rules: &Ruleset,
+) -> Vec<Wait> {
+    let score = |win_tile: Tile, win_method| {
+        if ctx.validate(win_method).is_err() {
+            return Scores { ranked: Vec::new() };
+        }
+
+        let hand = Hand {
+            tiles: [tiles, &[win_tile]].concat(),
+            melds: melds.to_vec(),
+            win_tile,
+            win_method,
+            dora: dora.to_vec(),
+            ura_dora: Vec::new(),
+        };
+
+        score_hand(&hand, player, table, ctx, rules)
+    };
+
+    winning_tiles(tiles, melds)
+        .into_iter()
+        .map(|tile| {
+            let ron = score(tile, WinMethod::Ron);
+            let tsumo = score(tile, WinMethod::Tsumo);
+
+            let mut shapes: Vec<WinWait> = ron
+                .ranked
+                .iter()
+                .chain(&tsumo.ranked)
+                .flat_map(|s| &s.mentsu)
+                .filter_map(|m| m.win_wait)
+                .collect();
+            shapes.sort();
+            shapes.dedup();
+
+            Wait {
+                tile,
+                shapes,
+                ron,
+                tsumo,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parser, test_util::NON_DEALER};
+
+    fn waits_of(s: &str, melds: &[Mentsu], ctx: WinContext) -> Vec<Wait> {
+        waits(
+            &tiles(s),
+            melds,
+            &[],
+            NON_DEALER,
+            TableState::default(),
+            ctx,
+            &Ruleset::default(),
+        )
+    }
+
+    fn tiles(s: &str) -> Vec<Tile> {
+        parser::parse_tiles(s).unwrap()
+    }
+
+    #[test]
+    fn one_tile_can_finish_several_shapes() {
+        let waits = waits_of("1123s456m789p333z", &[], WinContext::default());
+
+        assert_eq!(
+            waits.iter().map(|w| w.tile).collect::<Vec<_>>(),
+            tiles("1s4s")
+        );
+        assert_eq!(waits[0].shapes, [WinWait::Ryanmen, WinWait::Tanki]);
+        assert_eq!(waits[1].shapes, [WinWait::Ryanmen]);
+    }
+
+    #[test]
+    fn yaku_by_win_method() {
+        // West is no one's wind, so only a closed tsumo has a yaku.
+        let waits = waits_of("1123s456m789p333z", &[], WinContext::default());
+
+        assert!(waits.iter().all(|w| !w.has_yaku(WinMethod::Ron)));
+        assert!(waits.iter().all(|w| w.has_yaku(WinMethod::Tsumo)));
+    }
+
+    #[test]
+    fn context_can_rule_out_ron() {
+        let rinshan = WinContext {
+            rinshan: true,
+            ..Default::default()
+        };
+        let melds = parser::parse("(2222m)1123s789p333z 4s").unwrap().melds;
+        let waits = waits_of("1123s789p333z", &melds, rinshan);
+
+        assert_eq!(waits.len(), 2);
+        assert!(waits.iter().all(|w| w.ron.ranked.is_empty()));
+        assert!(waits.iter().all(|w| w.has_yaku(WinMethod::Tsumo)));
+    }
+
+    #[test]
+    fn not_tenpai() {
+        assert!(waits_of("1136s456m789p333z", &[], WinContext::default()).is_empty());
+        // The only wait is a fifth 1s.
+        assert!(winning_tiles(&tiles("1111s456m789p333z"), &[]).is_empty());
+    }
+}
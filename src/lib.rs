@@ -0,0 +1,49 @@
+//! Riichi mahjong hand scoring.
+//!
+//! A hand string is turned into a [`parser::Hand`] by [`parser::parse`],
+//! then [`score::score_hand`] scores every way of reading it and picks the
+//! highest-paying one:
+//!
+//! ```
+//! use mahjong_scoring::{
+//!     parser,
+//!     player_state::{PlayerState, Wind},
+//!     ruleset::Ruleset,
+//!     score,
+//!     table_state::TableState,
+//!     win_context::WinContext,
+//! };
+//!
+//! let hand = parser::parse("123m456m789p11s23s 4s").unwrap();
+//! let player = PlayerState {
+//!     seat_wind: Wind::South,
+//!     round_wind: Wind::East,
+//!     dealer: false,
+//! };
+//!
+//! let scores = score::score_hand(
+//!     &hand,
+//!     player,
+//!     TableState::default(),
+//!     WinContext::default(),
+//!     &Ruleset::default(),
+//! );
+//!
+//! let best = scores.best().unwrap();
+//! assert_eq!(best.han, 1);
+//! assert_eq!(best.payout.unwrap().payment.total(), 1000);
+//! ```
+
+pub mod analysis;
+pub mod mentsu;
+pub mod parser;
+pub mod player_state;
+pub mod ruleset;
+pub mod score;
+pub mod table_state;
+pub mod tile;
+pub mod win_context;
+pub mod yaku;
+
+#[cfg(test)]
+mod test_util;
@@ -1,73 +1,338 @@
-use self::{
-    mentsu::Mentsu,
+use std::path::PathBuf;
+
+use clap::Parser;
+use mahjong_scoring::{
+    analysis::{self, Discard, Pond},
+    parser,
     player_state::{PlayerState, Wind},
-    score::fu,
-    yaku::{REGULAR_YAKU, Yaku},
+    ruleset::{PRESETS, Ruleset},
+    score,
+    table_state::TableState,
+    tile::Tile,
+    win_context::WinContext,
 };
 
-mod mentsu;
-mod parser;
-mod player_state;
-mod score;
-mod tile;
-mod yaku;
+/// Scores a winning riichi mahjong hand, or reviews discards with --ukeire.
+#[derive(Parser, Debug)]
+#[command(version)]
+struct Args {
+    /// The hand, ending with the winning tile. Separate the winning tile
+    /// with a space for ron, e.g. "123m456m789p11s23s 4s".
+    /// Calls go in brackets: [123m] chi, [555p] pon, [7777s] open kan,
+    /// (1111z) closed kan, {2222m} added kan. 0 is a red five.
+    #[arg(required_unless_present = "print_rules")]
+    hand: Option<String>,
+
+    /// Seat wind: east, south, west or north. East is the dealer.
+    #[arg(long, default_value = "south", value_parser = parse_wind)]
+    seat: Wind,
+
+    /// Round wind: east, south, west or north.
+    #[arg(long, default_value = "east", value_parser = parse_wind)]
+    round: Wind,
+
+    /// Shorthand for `--seat east`.
+    #[arg(long, conflicts_with = "seat")]
+    dealer: bool,
+
+    /// Dora indicators, e.g. "1m5z".
+    #[arg(long)]
+    dora: Option<String>,
+
+    /// Ura-dora indicators. Only counted with riichi.
+    #[arg(long)]
+    ura: Option<String>,
+
+    /// Declared riichi.
+    #[arg(long)]
+    riichi: bool,
+
+    /// Declared riichi on the first discard.
+    #[arg(long)]
+    double_riichi: bool,
+
+    /// Won within one go-around of riichi.
+    #[arg(long)]
+    ippatsu: bool,
+
+    /// Won on the replacement tile after a kan.
+    #[arg(long)]
+    rinshan: bool,
+
+    /// Won on the last tile (haitei by tsumo, houtei by ron).
+    #[arg(long)]
+    haitei: bool,
+
+    /// Won by robbing a kan.
+    #[arg(long)]
+    chankan: bool,
+
+    /// Won on the first go-around with no calls (tenhou, chiihou).
+    #[arg(long)]
+    first_turn: bool,
+
+    /// Repeat counters on the table.
+    #[arg(long, default_value_t = 0)]
+    honba: u32,
+
+    /// The player's discards, checked for furiten on ron.
+    #[arg(long)]
+    discards: Option<String>,
+
+    /// Tiles others discarded since the player's last discard that they
+    /// didn't ron, checked for temporary furiten.
+    #[arg(long)]
+    missed: Option<String>,
+
+    /// Tiles others discarded since the player's riichi that they didn't
+    /// ron, checked for riichi furiten.
+    #[arg(long)]
+    missed_since_riichi: Option<String>,
+
+    /// Riichi deposits on the table.
+    #[arg(long, default_value_t = 0)]
+    riichi_sticks: u32,
+
+    /// Ruleset preset to score under.
+    #[arg(long, value_parser = clap::builder::PossibleValuesParser::new(PRESETS))]
+    preset: Option<String>,
+
+    /// Ruleset TOML file. Overrides `--preset`.
+    #[arg(long, conflicts_with = "preset")]
+    rules: Option<PathBuf>,
+
+    /// Print the ruleset in effect, after `--preset` or `--rules`, as TOML
+    /// and exit.
+    #[arg(long)]
+    print_rules: bool,
+
+    /// List every interpretation of the hand and its score.
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Print only the payment.
+    #[arg(short, long, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Print every interpretation and its score as JSON. Errors are
+    /// printed as `{"error": "..."}`.
+    #[arg(long, conflicts_with_all = ["verbose", "quiet"])]
+    json: bool,
+
+    /// Instead of scoring, show the shanten and tile acceptance left by
+    /// each discard. The last tile is the one just drawn.
+    #[arg(long, conflicts_with_all = ["verbose", "quiet"])]
+    ukeire: bool,
+
+    /// Tiles seen outside the hand, such as discards and other players'
+    /// calls, for --ukeire. Dora indicators are included automatically.
+    #[arg(long, requires = "ukeire")]
+    visible: Option<String>,
+}
+
+fn parse_wind(s: &str) -> Result<Wind, String> {
+    match s.to_ascii_lowercase().as_str() {
+        "e" | "east" => Ok(Wind::East),
+        "s" | "south" => Ok(Wind::South),
+        "w" | "west" => Ok(Wind::West),
+        "n" | "north" => Ok(Wind::North),
+        _ => Err("expected east, south, west or north".to_string()),
+    }
+}
 
 fn main() {
-    let s = "111222333s22m11z1z";
+    let args = Args::parse();
+    let json = args.json;
+
+    let seat_wind = if args.dealer { Wind::East } else { args.seat };
+
+    let player = PlayerState {
+        seat_wind,
+        round_wind: args.round,
+        dealer: seat_wind == Wind::East,
+    };
+
+    let table = TableState {
+        honba: args.honba,
+        riichi_sticks: args.riichi_sticks,
+    };
+
+    let ctx = WinContext {
+        riichi: args.riichi,
+        double_riichi: args.double_riichi,
+        ippatsu: args.ippatsu,
+        rinshan: args.rinshan,
+        haitei: args.haitei,
+        chankan: args.chankan,
+        first_turn: args.first_turn,
+    };
+
+    let rules = match (&args.rules, &args.preset) {
+        (Some(path), _) => Ruleset::load(path).unwrap_or_else(|e| {
+            fail(
+                json,
+                &format!("Failed to load ruleset {}: {e}", path.display()),
+            )
+        }),
+        // Presets are checked by clap.
+        (None, Some(name)) => Ruleset::preset(name).unwrap_or_default(),
+        (None, None) => Ruleset::default(),
+    };
+
+    if args.print_rules {
+        print!("{rules}");
+        return;
+    }
+
+    // Required by clap unless printing the rules.
+    let hand_str = args.hand.as_deref().unwrap_or_default();
+
+    let mut hand = parser::parse(hand_str)
+        .unwrap_or_else(|e| fail(json, &format!("Failed to parse \"{hand_str}\": {e}")));
+
+    hand.dora = tiles_arg(args.dora.as_deref(), "dora indicators", json);
+    hand.ura_dora = tiles_arg(args.ura.as_deref(), "ura-dora indicators", json);
+
+    if args.ukeire {
+        let mut visible = tiles_arg(args.visible.as_deref(), "visible tiles", json);
+        visible.extend(&hand.dora);
+
+        let discards = analysis::ukeire(&hand.tiles, &hand.melds, &visible);
+
+        if json {
+            println!(
+                "{}",
+                serde_json::to_string(&discards).expect("discards always serialize")
+            );
+        } else {
+            print_ukeire(&discards);
+        }
+
+        return;
+    }
 
-    let player = player_state::PlayerState {
-        seat_wind: Wind::South,
-        round_wind: Wind::East,
-        dealer: false,
+    if let Err(e) = ctx.validate(hand.win_method) {
+        fail(json, &format!("Impossible win: {e}"));
+    }
+
+    if args.missed_since_riichi.is_some() && !ctx.any_riichi() {
+        fail(
+            json,
+            "--missed-since-riichi needs --riichi or --double-riichi",
+        );
+    }
+
+    let pond = Pond {
+        discards: tiles_arg(args.discards.as_deref(), "discards", json),
+        missed: tiles_arg(args.missed.as_deref(), "missed tiles", json),
+        missed_since_riichi: tiles_arg(args.missed_since_riichi.as_deref(), "missed tiles", json),
     };
 
-    let parser::Hand {
-        tiles,
-        win_tile,
-        win_method,
-    } = parser::parse(s);
+    if let Some(f) = analysis::ron_furiten(&hand, &pond, ctx).first() {
+        fail(json, &format!("Can't ron: {f}"));
+    }
+
+    let scores = score::score_hand(&hand, player, table, ctx, &rules);
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string(&scores).expect("scores always serialize")
+        );
 
-    let i13s = parser::interpret(&tiles, win_tile, win_method);
+        if scores.best().is_none() {
+            std::process::exit(1);
+        }
+
+        return;
+    }
 
-    println!("{} winning interpretation(s):", i13s.len());
-    for hand in &i13s {
-        for m in hand {
-            print!("{m}, ");
+    if args.verbose {
+        println!("{} interpretation(s):", scores.ranked.len());
+        for score in &scores.ranked {
+            for m in &score.mentsu {
+                print!("{m}, ");
+            }
+            match score.payout {
+                Some(payout) if score.yakuman > 0 => {
+                    println!("=> {}x yakuman, {}", score.yakuman, payout.payment);
+                }
+                Some(payout) => println!("=> {} han, {}", score.han, payout.payment),
+                None => println!("=> no yaku"),
+            }
         }
         println!();
     }
 
-    println!(
-        "{} fu",
-        i13s.iter().map(|hand| fu(hand, win_method)).max().unwrap()
-    );
+    let Some((best, payout)) = scores.best().and_then(|b| Some((b, b.payout?))) else {
+        fail(json, "Not a winning hand");
+    };
+
+    if args.quiet {
+        println!("{}", payout.payment);
+        return;
+    }
+
+    for y in &best.yaku {
+        println!("{}", y.name);
+    }
+
+    let dora = best.dora;
+    if dora.total() > 0 {
+        println!("Dora {}, ura {}, aka {}", dora.dora, dora.ura, dora.aka);
+    }
+
+    println!();
+
+    if best.yakuman > 0 {
+        println!("{}x yakuman", best.yakuman);
+    } else {
+        println!("{}", best.fu);
+        println!();
+        println!("{} han {} fu", best.han, best.fu.total());
 
-    println!("Weirdos?");
-    for w in yaku::WEIRD_YAKU {
-        if (w.f)(&tiles) {
-            println!("{}", w.name);
+        if let Some(limit) = payout.limit {
+            println!("{limit}");
         }
     }
 
-    println!("Best yaku combo:");
-    for yaku in i13s
-        .iter()
-        .map(|hand| valid_yaku(hand, player))
-        .max_by_key(|y| y.iter().map(|y| y.han(true)).sum::<u32>())
-        .unwrap()
-    {
-        println!("{}", yaku.name);
+    println!("{}", payout.payment);
+
+    if payout.deposits > 0 {
+        println!("+{} from riichi deposits", payout.deposits);
     }
 }
 
-fn valid_yaku(hand: &[Mentsu], player: PlayerState) -> Vec<&Yaku> {
-    let mut ans = Vec::new();
+fn print_ukeire(discards: &[Discard]) {
+    println!("Discard  Shanten  Unseen  Accepts");
 
-    for y in REGULAR_YAKU {
-        if y.valid_for(hand, player) {
-            ans.push(y);
-        }
+    for d in discards {
+        let accepts: String = d.accepts.iter().map(|(t, _)| t.to_string()).collect();
+
+        println!(
+            "{:<7}  {:>7}  {:>6}  {accepts}",
+            d.tile.to_string(),
+            d.shanten,
+            d.available
+        );
+    }
+}
+
+fn tiles_arg(s: Option<&str>, what: &str, json: bool) -> Vec<Tile> {
+    let Some(s) = s else {
+        return Vec::new();
+    };
+
+    parser::parse_tiles(s)
+        .unwrap_or_else(|e| fail(json, &format!("Failed to parse {what} \"{s}\": {e}")))
+}
+
+fn fail(json: bool, msg: &str) -> ! {
+    if json {
+        println!("{}", serde_json::json!({ "error": msg }));
+    } else {
+        eprintln!("{msg}");
     }
 
-    ans
+    std::process::exit(1);
 }
@@ -0,0 +1,37 @@
+use serde::Serialize;
+
+/// How a declared mentsu was formed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub enum Call {
+    Chi,
+    Pon,
+    /// Daiminkan: a kan called from another player's discard.
+    OpenKan,
+    /// Ankan: a kan declared from four concealed tiles.
+    ClosedKan,
+    /// Shouminkan: a fourth tile added to an existing pon.
+    AddedKan,
+}
+
+impl Call {
+    /// Returns `true` if this call opens the hand.
+    pub const fn opens_hand(self) -> bool {
+        !matches!(self, Self::ClosedKan)
+    }
+}
+
+impl std::fmt::Display for Call {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Chi => "Chi",
+                Self::Pon => "Pon",
+                Self::OpenKan => "Daiminkan",
+                Self::ClosedKan => "Ankan",
+                Self::AddedKan => "Shouminkan",
+            }
+        )
+    }
+}
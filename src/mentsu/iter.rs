@@ -3,6 +3,7 @@ use crate::tile::Tile;
 
 use super::Mentsu;
 
+/// Iterator over a mentsu's tiles. See [`Mentsu::iter`].
 pub struct Iter {
     tiles: Vec<Tile>,
     next_idx: usize,
@@ -10,11 +11,23 @@ pub struct Iter {
 
 impl Iter {
     pub fn new(m: &Mentsu) -> Self {
-        let tiles = match m.kind {
-            Kind::Triplet(tile) | Kind::Quad(tile) | Kind::Pair(tile) => vec![tile; 3],
-            Kind::Sequence(t0, t1, t2) => vec![t0, t1, t2],
+        let (tile, copies) = match m.kind {
+            Kind::Sequence(t0, t1, t2) => {
+                return Self {
+                    tiles: vec![t0, t1, t2],
+                    next_idx: 0,
+                };
+            }
+            Kind::Single(tile) => (tile, 1),
+            Kind::Pair(tile) => (tile, 2),
+            Kind::Triplet(tile) => (tile, 3),
+            Kind::Quad(tile) => (tile, 4),
         };
 
+        // Only the first copy keeps the red five flag.
+        let mut tiles = vec![Tile::new(tile.value, tile.suit); copies];
+        tiles[0] = tile;
+
         Self { tiles, next_idx: 0 }
     }
 }
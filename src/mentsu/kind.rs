@@ -1,11 +1,15 @@
 use crate::tile::Tile;
+use serde::Serialize;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// The shape of a mentsu. Each variant holds its tiles, lowest first.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
 pub enum Kind {
     Triplet(Tile),
     Quad(Tile),
     Sequence(Tile, Tile, Tile),
     Pair(Tile),
+    /// A lone tile. Only appears in kokushi musou.
+    Single(Tile),
 }
 
 impl std::fmt::Display for Kind {
@@ -15,6 +19,7 @@ impl std::fmt::Display for Kind {
             Self::Quad(tile) => format!("Qd({tile})"),
             Self::Sequence(t0, t1, t2) => format!("Sq({t0},{t1},{t2})"),
             Self::Pair(tile) => format!("Pr({tile})"),
+            Self::Single(tile) => format!("Sg({tile})"),
         };
 
         write!(f, "{s}")
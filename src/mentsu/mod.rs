@@ -1,30 +1,33 @@
+//! Sets, pairs and the other pieces a hand is built from.
+use self::call::Call;
 use self::kind::Kind;
 use crate::parser::WinWait;
-use crate::player_state::Wind;
+use crate::player_state::{PlayerState, Wind};
 use crate::tile::{Suit, Tile};
+use serde::Serialize;
 
+pub mod call;
 mod iter;
 pub mod kind;
 
-#[derive(Debug, Copy, Clone, Eq, PartialOrd, Ord)]
+pub use iter::Iter;
+
+/// A set, pair or single that makes up part of a hand.
+///
+/// Mentsu compare equal only if every field matches. Compare `kind` to
+/// check for the same shape alone.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
 pub struct Mentsu {
     pub kind: Kind,
+    /// Called from a discard, or completed by ron.
     pub open: bool,
+    /// Set on the mentsu the winning tile completed.
     pub win_wait: Option<WinWait>,
-}
-
-impl PartialEq for Mentsu {
-    fn eq(&self, other: &Self) -> bool {
-        self.kind == other.kind
-    }
-}
-
-impl std::hash::Hash for Mentsu {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.kind.hash(state);
-        self.open.hash(state);
-        self.win_wait.hash(state);
-    }
+    /// The winning tile, set alongside `win_wait`. A ryanmen wait alone
+    /// doesn't say which end was won on.
+    pub win_tile: Option<Tile>,
+    /// Set if this mentsu was declared rather than drawn concealed.
+    pub call: Option<Call>,
 }
 
 impl std::fmt::Display for Mentsu {
@@ -51,12 +54,25 @@ impl Mentsu {
             kind,
             open: false,
             win_wait: None,
+            win_tile: None,
+            call: None,
+        }
+    }
+
+    /// Creates a declared mentsu. It is open unless it is a closed kan.
+    pub const fn called(kind: Kind, call: Call) -> Self {
+        Self {
+            kind,
+            open: call.opens_hand(),
+            win_wait: None,
+            win_tile: None,
+            call: Some(call),
         }
     }
 
     pub fn contains(self, t: Tile) -> bool {
         match self.kind {
-            Kind::Triplet(h) | Kind::Quad(h) | Kind::Pair(h) => t == h,
+            Kind::Triplet(h) | Kind::Quad(h) | Kind::Pair(h) | Kind::Single(h) => t == h,
             Kind::Sequence(h0, h1, h2) => t == h0 || t == h1 || t == h2,
         }
     }
@@ -65,13 +81,30 @@ impl Mentsu {
         self.win_wait = val;
     }
 
+    /// Marks a copy of `t` in this mentsu as a red five. Returns `false` if
+    /// there is no unmarked copy to mark.
+    pub fn mark_aka(&mut self, t: Tile) -> bool {
+        let slot = match &mut self.kind {
+            Kind::Triplet(h) | Kind::Quad(h) | Kind::Pair(h) | Kind::Single(h) => Some(h),
+            Kind::Sequence(h0, h1, h2) => [h0, h1, h2].into_iter().find(|h| **h == t),
+        };
+
+        match slot {
+            Some(h) if *h == t && !h.aka => {
+                h.aka = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub const fn set_open(&mut self, val: bool) {
         self.open = val;
     }
 
     pub fn contains_terminal(self) -> bool {
         match self.kind {
-            Kind::Triplet(t) | Kind::Quad(t) | Kind::Pair(t) => t.terminal(),
+            Kind::Triplet(t) | Kind::Quad(t) | Kind::Pair(t) | Kind::Single(t) => t.terminal(),
             Kind::Sequence(t0, t1, t2) => t0.terminal() || t1.terminal() || t2.terminal(),
         }
     }
@@ -82,24 +115,39 @@ impl Mentsu {
 
     pub fn entirely_terminal(self) -> bool {
         match self.kind {
-            Kind::Triplet(t) | Kind::Quad(t) | Kind::Pair(t) => t.terminal(),
+            Kind::Triplet(t) | Kind::Quad(t) | Kind::Pair(t) | Kind::Single(t) => t.terminal(),
             Kind::Sequence(_, _, _) => false,
         }
     }
 
     pub const fn suit(self) -> Suit {
         match self.kind {
-            Kind::Triplet(t) | Kind::Quad(t) | Kind::Pair(t) | Kind::Sequence(t, _, _) => t.suit,
+            Kind::Triplet(t)
+            | Kind::Quad(t)
+            | Kind::Pair(t)
+            | Kind::Single(t)
+            | Kind::Sequence(t, _, _) => t.suit,
         }
     }
 
     pub fn honor(self) -> bool {
         match self.kind {
-            Kind::Triplet(t) | Kind::Quad(t) | Kind::Pair(t) => t.honor(),
+            Kind::Triplet(t) | Kind::Quad(t) | Kind::Pair(t) | Kind::Single(t) => t.honor(),
             Kind::Sequence(_, _, _) => false,
         }
     }
 
+    pub fn dragon(self) -> bool {
+        match self.kind {
+            Kind::Triplet(t) | Kind::Quad(t) | Kind::Pair(t) | Kind::Single(t) => t.dragon(),
+            Kind::Sequence(_, _, _) => false,
+        }
+    }
+
+    pub const fn single(self) -> bool {
+        matches!(self.kind, Kind::Single(_))
+    }
+
     pub const fn pair(self) -> bool {
         matches!(self.kind, Kind::Pair(_))
     }
@@ -118,20 +166,19 @@ impl Mentsu {
 
     /// Returns `true` if and only if both mentsu fulfill all 3 conditions:
     ///
-    /// (1) Same type (e.g. triplet, pair)
+    /// (1) Same type (e.g. triplet, pair), where quads count as triplets
     /// (2) Identical values
     /// (3) Different suits
     ///
     /// Used for scoring sanshoku doujun and sanshoku doukou.
     pub fn eq_diff_suits(self, other: Self) -> bool {
-        if self.suit() == other.suit() {
+        if self.suit() == other.suit() || self.honor() || other.honor() {
             return false;
         }
 
         match (self.kind, other.kind) {
             (Kind::Sequence(a, _, _), Kind::Sequence(b, _, _))
-            | (Kind::Triplet(a), Kind::Triplet(b))
-            | (Kind::Quad(a), Kind::Quad(b))
+            | (Kind::Triplet(a) | Kind::Quad(a), Kind::Triplet(b) | Kind::Quad(b))
             | (Kind::Pair(a), Kind::Pair(b)) => a.value == b.value,
             _ => false,
         }
@@ -139,12 +186,26 @@ impl Mentsu {
 
     pub fn wind(self) -> Option<Wind> {
         match self.kind {
-            Kind::Triplet(t) | Kind::Quad(t) | Kind::Pair(t) => t.wind(),
+            Kind::Triplet(t) | Kind::Quad(t) | Kind::Pair(t) | Kind::Single(t) => t.wind(),
             Kind::Sequence(_, _, _) => None,
         }
     }
 
-    pub fn iter(&self) -> self::iter::Iter {
+    /// Returns `true` if this mentsu is made of dragons or either of the
+    /// player's value winds.
+    pub fn yakuhai(self, player: PlayerState) -> bool {
+        match self.kind {
+            Kind::Triplet(t) | Kind::Quad(t) | Kind::Pair(t) | Kind::Single(t) => {
+                t.dragon()
+                    || t.wind()
+                        .is_some_and(|w| w == player.seat_wind || w == player.round_wind)
+            }
+            Kind::Sequence(_, _, _) => false,
+        }
+    }
+
+    /// Iterates over every tile in the mentsu, including repeats.
+    pub fn iter(&self) -> Iter {
         self::iter::Iter::new(self)
     }
 }
@@ -154,3 +215,15 @@ pub fn get_tiles(ms: &[Mentsu]) -> Vec<Tile> {
         .flat_map(|m| m.iter().collect::<Vec<Tile>>())
         .collect()
 }
+
+/// Returns `true` if no mentsu was called from another player's discard.
+/// Mentsu completed by ron don't count as called.
+pub fn menzenchin(ms: &[Mentsu]) -> bool {
+    !ms.iter().any(|m| m.open && m.win_wait.is_none())
+}
+
+/// Returns `true` if the winning tile was self-drawn. On a ron, the mentsu
+/// completed by the winning tile is marked open.
+pub fn tsumo(ms: &[Mentsu]) -> bool {
+    ms.iter().any(|m| m.win_wait.is_some() && !m.open)
+}
@@ -0,0 +1,105 @@
+use crate::tile::Tile;
+
+/// Reasons a hand string can fail to parse.
+///
+/// Every variant that points at a specific spot in the input carries the
+/// byte `offset` of the offending character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The input contains no tiles at all.
+    Empty,
+    /// A letter that isn't one of `m`, `p`, `s` or `z`.
+    UnknownSuit { offset: usize, found: char },
+    /// A tile value that doesn't exist in its suit, e.g. `8z`.
+    ValueOutOfRange { offset: usize, value: u32 },
+    /// Digits that are never followed by a suit letter.
+    DanglingDigits { offset: usize },
+    /// A suit letter with no digits in front of it.
+    SuitWithoutDigits { offset: usize },
+    /// A closing bracket with no matching opening bracket, or a nested one.
+    UnmatchedBracket { offset: usize, found: char },
+    /// A meld bracket that is never closed.
+    UnclosedMeld { offset: usize },
+    /// Bracketed tiles that don't form a chi, pon or kan of that bracket type.
+    InvalidMeld { offset: usize },
+    /// The number of concealed tiles doesn't make a complete hand alongside
+    /// the called melds.
+    TileCount { expected: usize, found: usize },
+    /// The hand ends with a meld rather than the winning tile.
+    MissingWinTile { offset: usize },
+    /// A fifth copy of a tile, counting called melds. `offset` points at
+    /// the fifth copy.
+    TooManyCopies { offset: usize, tile: Tile },
+    /// A second red five in the same suit, counting called melds. `offset`
+    /// points at the second one.
+    DuplicateRedFive { offset: usize, tile: Tile },
+}
+
+impl ParseError {
+    /// Returns the byte offset the error points at, if any.
+    pub const fn offset(&self) -> Option<usize> {
+        match self {
+            Self::Empty | Self::TileCount { .. } => None,
+            Self::UnknownSuit { offset, .. }
+            | Self::ValueOutOfRange { offset, .. }
+            | Self::DanglingDigits { offset }
+            | Self::SuitWithoutDigits { offset }
+            | Self::UnmatchedBracket { offset, .. }
+            | Self::UnclosedMeld { offset }
+            | Self::InvalidMeld { offset }
+            | Self::MissingWinTile { offset }
+            | Self::TooManyCopies { offset, .. }
+            | Self::DuplicateRedFive { offset, .. } => Some(*offset),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "hand is empty"),
+            Self::UnknownSuit { offset, found } => write!(
+                f,
+                "unknown suit '{found}' at byte {offset} (expected m, p, s or z)"
+            ),
+            Self::ValueOutOfRange { offset, value } => {
+                write!(f, "tile value {value} at byte {offset} is out of range")
+            }
+            Self::DanglingDigits { offset } => {
+                write!(f, "digits starting at byte {offset} have no suit")
+            }
+            Self::SuitWithoutDigits { offset } => {
+                write!(f, "suit at byte {offset} has no tile values")
+            }
+            Self::UnmatchedBracket { offset, found } => {
+                write!(f, "unmatched '{found}' at byte {offset}")
+            }
+            Self::UnclosedMeld { offset } => {
+                write!(f, "meld opened at byte {offset} is never closed")
+            }
+            Self::InvalidMeld { offset } => write!(
+                f,
+                "meld at byte {offset} is not a valid chi, pon or kan for its brackets"
+            ),
+            Self::TileCount { expected, found } => write!(
+                f,
+                "expected {expected} concealed tiles (including the winning tile), found {found}"
+            ),
+            Self::MissingWinTile { offset } => write!(
+                f,
+                "hand ends with the meld at byte {offset} instead of the winning tile"
+            ),
+            Self::TooManyCopies { offset, tile } => write!(
+                f,
+                "fifth copy of {tile} at byte {offset} (there are only four of each tile)"
+            ),
+            Self::DuplicateRedFive { offset, tile } => write!(
+                f,
+                "second red five {tile} at byte {offset} (each suit has only one)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
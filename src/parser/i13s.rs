@@ -7,7 +7,12 @@ use std::collections::{BTreeMap, HashSet};
 
 use super::WinMethod;
 
-pub fn build(as_tiles: &[Tile], win_tile: Tile, win_method: WinMethod) -> Vec<Vec<Mentsu>> {
+pub fn build(
+    as_tiles: &[Tile],
+    melds: &[Mentsu],
+    win_tile: Tile,
+    win_method: WinMethod,
+) -> Vec<Vec<Mentsu>> {
     let mut counts: BTreeMap<Tile, u32> = BTreeMap::new();
 
     for t in as_tiles {
@@ -16,21 +21,62 @@ pub fn build(as_tiles: &[Tile], win_tile: Tile, win_method: WinMethod) -> Vec<Ve
 
     let mut basic = rec_build(&counts, 0, &[]);
 
-    // Keep only winning hands
+    // The counts above merge red fives with regular ones, so put them back.
+    for hand in &mut basic {
+        for &aka in as_tiles.iter().filter(|t| t.aka) {
+            hand.iter_mut().any(|m| m.mark_aka(aka));
+        }
+    }
+
+    // Keep only winning hands. Called melds fill in the missing sets.
     basic.retain(|v| {
-        v.iter()
+        let pairs = v.iter().filter(|m| matches!(m.kind, Kind::Pair(_))).count();
+        let sets = v
+            .iter()
             .filter(|m| {
                 matches!(
                     m.kind,
                     Kind::Triplet(_) | Kind::Quad(_) | Kind::Sequence(_, _, _)
                 )
             })
-            .count()
-            == 4
-            && v.iter().filter(|m| matches!(m.kind, Kind::Pair(_))).count() == 1
+            .count();
+
+        sets + melds.len() == 4 && pairs == 1 || seven_pairs(v) && melds.is_empty()
     });
 
-    basic_to_open(&basic, win_tile, win_method)
+    if melds.is_empty()
+        && let Some(hand) = kokushi(&counts)
+    {
+        basic.push(hand);
+    }
+
+    basic_to_open(&basic, melds, win_tile, win_method)
+}
+
+/// Chiitoitsu: seven pairs, no two alike.
+fn seven_pairs(hand: &[Mentsu]) -> bool {
+    let mut pairs: Vec<Kind> = hand.iter().filter(|m| m.pair()).map(|m| m.kind).collect();
+
+    pairs.dedup();
+    pairs.len() == 7 && hand.len() == 7
+}
+
+/// Kokushi musou: one of every terminal and honor, plus a second copy of
+/// one of them. Returns the hand as twelve singles and a pair.
+fn kokushi(counts: &BTreeMap<Tile, u32>) -> Option<Vec<Mentsu>> {
+    if counts.len() != 13 || counts.keys().any(|t| t.simple()) {
+        return None;
+    }
+
+    Some(
+        counts
+            .iter()
+            .map(|(&t, &n)| match n {
+                2 => Mentsu::new(Kind::Pair(t)),
+                _ => Mentsu::new(Kind::Single(t)),
+            })
+            .collect(),
+    )
 }
 
 /// Recursively computes possible interpretations of a hand.
@@ -69,21 +115,14 @@ fn rec_build(counts: &BTreeMap<Tile, u32>, i: usize, mentsu_rn: &[Mentsu]) -> Ve
         }
     }
 
-    // Quad
-    if this_count >= 4 {
-        for m in rec_build(
-            &decrement(counts, &[this; 4]),
-            i,
-            &with(mentsu_rn, Mentsu::new(Kind::Quad(this))),
-        ) {
-            ans.push(m);
-        }
-    }
+    // Quads are never built here: kans must be declared as called melds,
+    // so four concealed copies are a triplet plus a spare tile.
 
     // Sequence
-    if this
-        .add(1)
-        .is_some_and(|t| counts.get(&t).is_some_and(|v| *v >= 1))
+    if !this.honor()
+        && this
+            .add(1)
+            .is_some_and(|t| counts.get(&t).is_some_and(|v| *v >= 1))
         && this
             .add(2)
             .is_some_and(|t| counts.get(&t).is_some_and(|v| *v >= 1))
@@ -107,14 +146,23 @@ fn rec_build(counts: &BTreeMap<Tile, u32>, i: usize, mentsu_rn: &[Mentsu]) -> Ve
     ans
 }
 
-fn basic_to_open(i13s: &[Vec<Mentsu>], win_tile: Tile, win_method: WinMethod) -> Vec<Vec<Mentsu>> {
+/// Assigns the win wait to each concealed mentsu that could have been
+/// completed by the winning tile, then adds the called melds. Called melds
+/// are never considered for the wait.
+fn basic_to_open(
+    i13s: &[Vec<Mentsu>],
+    melds: &[Mentsu],
+    win_tile: Tile,
+    win_method: WinMethod,
+) -> Vec<Vec<Mentsu>> {
     let mut ans: HashSet<Vec<Mentsu>> = HashSet::new();
 
     let mut i13s = i13s.to_owned();
 
     for hand in &mut i13s {
         for (i, m) in hand.iter().enumerate() {
-            if m.contains(win_tile) {
+            // Kans are declared before the win, so they can't be the wait.
+            if m.contains(win_tile) && !m.quad() {
                 // Push a copy of this hand with this mentsu as open.
                 let mut h = hand.clone();
 
@@ -122,11 +170,14 @@ fn basic_to_open(i13s: &[Vec<Mentsu>], win_tile: Tile, win_method: WinMethod) ->
                     h[i].set_open(true);
                 }
 
+                h[i].win_tile = Some(win_tile);
+
                 let mut set_wait = |wait| h[i].set_win_wait(Some(wait));
 
                 match m.kind {
-                    // Tanki
-                    Kind::Pair(_) => set_wait(WinWait::Tanki),
+                    // Tanki. For kokushi, waiting on the pair is the
+                    // thirteen-sided wait.
+                    Kind::Pair(_) | Kind::Single(_) => set_wait(WinWait::Tanki),
 
                     // Shanpon
                     Kind::Triplet(_) => set_wait(WinWait::Shanpon),
@@ -147,10 +198,11 @@ fn basic_to_open(i13s: &[Vec<Mentsu>], win_tile: Tile, win_method: WinMethod) ->
                     // Ryanmen
                     Kind::Sequence(_, _, _) => set_wait(WinWait::Ryanmen),
 
-                    // Where do quads fit into this? idk
-                    Kind::Quad(_) => unimplemented!(),
+                    Kind::Quad(_) => unreachable!(),
                 }
 
+                h.extend_from_slice(melds);
+
                 // By sorting before inserting, hands are effectively
                 // hashed/compared as unordered collections.
                 h.sort();
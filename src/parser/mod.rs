@@ -1,62 +1,474 @@
-use crate::mentsu::Mentsu;
+//! Reading hand strings, and breaking hands down into mentsu.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::mentsu::{Mentsu, call::Call, kind::Kind};
 use crate::tile::Suit;
 use crate::tile::Tile;
 
+mod error;
 mod i13s;
 mod win_method;
 mod win_wait;
 
+pub use error::ParseError;
 pub use win_method::WinMethod;
 pub use win_wait::WinWait;
 
+/// A parsed hand, before it is broken down into mentsu.
 pub struct Hand {
+    /// Concealed tiles, including the winning tile.
     pub tiles: Vec<Tile>,
+    /// Called melds, which are never broken up during interpretation.
+    pub melds: Vec<Mentsu>,
     pub win_tile: Tile,
     pub win_method: WinMethod,
+    /// Dora indicators. Not part of the hand string; see [`parse_tiles`].
+    pub dora: Vec<Tile>,
+    /// Ura-dora indicators, only counted for riichi hands.
+    pub ura_dora: Vec<Tile>,
 }
 
-pub fn parse(s: &str) -> Hand {
-    let mut suit_vals: Vec<u32> = Vec::new();
+/// Parses a hand string such as `"111222333s22m11z1z"`.
+///
+/// The last tile is the winning tile. Separating it from the rest of the
+/// hand with whitespace, as in `"111222333s22m11z 1z"`, marks the win as
+/// ron; otherwise it is a tsumo.
+///
+/// Called melds are written in brackets before the winning tile:
+///
+/// - `[123m]` chi, `[555p]` pon, `[7777s]` open kan (daiminkan)
+/// - `(1111z)` closed kan (ankan)
+/// - `{2222m}` added kan (shouminkan)
+///
+/// For example, `"234m5p[678s][777z](1111z) 5p"` wins by ron on 5p.
+///
+/// A `0` in the man, pin or sou suits is a red five.
+pub fn parse(s: &str) -> Result<Hand, ParseError> {
+    let s = s.trim_end();
+
     let mut tiles: Vec<Tile> = Vec::new();
+    let mut melds: Vec<Mentsu> = Vec::new();
 
-    let (win_tile, win_method) = {
-        let t = Tile::new(
-            s.chars().nth(s.len() - 2).unwrap().to_digit(10).unwrap(),
-            Suit::from(s.chars().nth(s.len() - 1).unwrap()),
-        );
+    // Byte offset and value of each digit awaiting a suit.
+    let mut suit_vals: Vec<(usize, u32)> = Vec::new();
+
+    // Every tile read, concealed or called, with its offset.
+    let mut read: Vec<(usize, Tile)> = Vec::new();
+
+    // Opening offset, bracket and tiles of the meld being read.
+    let mut meld: Option<(usize, char, Vec<Tile>)> = None;
+
+    // Whether whitespace came right before the digit being read, and
+    // before the last concealed digit, which is the winning tile's.
+    let mut after_space = false;
+    let mut win_spaced = false;
+
+    // Offset of the last meld, if nothing but whitespace follows it. The
+    // winning tile can't be part of a meld.
+    let mut last_meld: Option<usize> = None;
 
-        match s.chars().nth(s.len() - 3) {
-            Some(c) if c.is_whitespace() => (t, WinMethod::Ron),
-            _ => (t, WinMethod::Tsumo),
+    for (i, c) in s.char_indices() {
+        if let Some(val) = c.to_digit(10) {
+            if meld.is_none() {
+                win_spaced = after_space;
+            }
+            after_space = false;
+            suit_vals.push((i, val));
+            continue;
         }
-    };
 
-    dbg!((win_tile, win_method));
+        if let Some(&(offset, _)) = suit_vals.first()
+            && !c.is_alphabetic()
+        {
+            return Err(ParseError::DanglingDigits { offset });
+        }
 
-    for c in s.chars() {
-        if c.is_ascii_digit() {
-            suit_vals.push(c.to_digit(10).unwrap());
+        if c.is_whitespace() {
+            after_space = true;
             continue;
         }
 
-        for val in &suit_vals {
-            tiles.push(Tile::new(*val, Suit::from(c)));
+        if matches!(c, '[' | '(' | '{') {
+            if meld.is_some() {
+                return Err(ParseError::UnmatchedBracket {
+                    offset: i,
+                    found: c,
+                });
+            }
+            meld = Some((i, c, Vec::new()));
+            continue;
+        }
+
+        if matches!(c, ']' | ')' | '}') {
+            let Some((start, open, meld_tiles)) = meld.take() else {
+                return Err(ParseError::UnmatchedBracket {
+                    offset: i,
+                    found: c,
+                });
+            };
+
+            if closing_bracket(open) != c {
+                return Err(ParseError::UnmatchedBracket {
+                    offset: i,
+                    found: c,
+                });
+            }
+
+            melds
+                .push(to_meld(open, &meld_tiles).ok_or(ParseError::InvalidMeld { offset: start })?);
+
+            last_meld = Some(start);
+            after_space = false;
+            continue;
+        }
+
+        let suit =
+            Suit::try_from(c).map_err(|found| ParseError::UnknownSuit { offset: i, found })?;
+
+        if suit_vals.is_empty() {
+            return Err(ParseError::SuitWithoutDigits { offset: i });
+        }
+
+        for &(offset, value) in &suit_vals {
+            let tile = to_tile(offset, value, suit)?;
+
+            read.push((offset, tile));
+
+            match &mut meld {
+                Some((_, _, meld_tiles)) => meld_tiles.push(tile),
+                None => tiles.push(tile),
+            }
+        }
+
+        if meld.is_none() {
+            last_meld = None;
         }
 
         suit_vals.clear();
+        after_space = false;
+    }
+
+    if let Some(&(offset, _)) = suit_vals.first() {
+        return Err(ParseError::DanglingDigits { offset });
+    }
+
+    if let Some((offset, _, _)) = meld {
+        return Err(ParseError::UnclosedMeld { offset });
     }
 
+    if let Some(offset) = last_meld {
+        return Err(ParseError::MissingWinTile { offset });
+    }
+
+    let Some(&win_tile) = tiles.last() else {
+        return Err(ParseError::Empty);
+    };
+
+    // Each meld stands in for one set, however many tiles it holds.
+    let expected = 14usize.saturating_sub(3 * melds.len());
+
+    if tiles.len() != expected {
+        return Err(ParseError::TileCount {
+            expected,
+            found: tiles.len(),
+        });
+    }
+
+    // Red fives share their copies with the regular fives.
+    let mut copies: HashMap<Tile, u8> = HashMap::new();
+    let mut red_suits: HashSet<Suit> = HashSet::new();
+
+    for (offset, tile) in read {
+        let n = copies.entry(tile).or_default();
+        *n += 1;
+
+        if *n > 4 {
+            return Err(ParseError::TooManyCopies { offset, tile });
+        }
+
+        if tile.aka && !red_suits.insert(tile.suit) {
+            return Err(ParseError::DuplicateRedFive { offset, tile });
+        }
+    }
+
+    let win_method = if win_spaced {
+        WinMethod::Ron
+    } else {
+        WinMethod::Tsumo
+    };
+
     tiles.sort();
 
-    Hand {
+    Ok(Hand {
         tiles,
+        melds,
         win_tile,
         win_method,
+        dora: Vec::new(),
+        ura_dora: Vec::new(),
+    })
+}
+
+/// Parses a plain list of tiles such as `"1z 35m"`, e.g. dora indicators.
+/// Melds and the winning tile have no meaning here.
+pub fn parse_tiles(s: &str) -> Result<Vec<Tile>, ParseError> {
+    let mut tiles: Vec<Tile> = Vec::new();
+    let mut suit_vals: Vec<(usize, u32)> = Vec::new();
+
+    for (i, c) in s.char_indices() {
+        if let Some(val) = c.to_digit(10) {
+            suit_vals.push((i, val));
+            continue;
+        }
+
+        if let Some(&(offset, _)) = suit_vals.first()
+            && !c.is_alphabetic()
+        {
+            return Err(ParseError::DanglingDigits { offset });
+        }
+
+        if c.is_whitespace() {
+            continue;
+        }
+
+        let suit =
+            Suit::try_from(c).map_err(|found| ParseError::UnknownSuit { offset: i, found })?;
+
+        if suit_vals.is_empty() {
+            return Err(ParseError::SuitWithoutDigits { offset: i });
+        }
+
+        for &(offset, value) in &suit_vals {
+            tiles.push(to_tile(offset, value, suit)?);
+        }
+
+        suit_vals.clear();
+    }
+
+    if let Some(&(offset, _)) = suit_vals.first() {
+        return Err(ParseError::DanglingDigits { offset });
+    }
+
+    Ok(tiles)
+}
+
+/// Validates a digit read at `offset` as a tile of `suit`.
+fn to_tile(offset: usize, value: u32, suit: Suit) -> Result<Tile, ParseError> {
+    if value == 0 && suit != Suit::Honor {
+        return Ok(Tile::new_aka(suit));
+    }
+
+    if !(1..=suit.max_value()).contains(&value) {
+        return Err(ParseError::ValueOutOfRange { offset, value });
+    }
+
+    Ok(Tile::new(value, suit))
+}
+
+const fn closing_bracket(open: char) -> char {
+    match open {
+        '(' => ')',
+        '{' => '}',
+        _ => ']',
     }
 }
 
-pub fn interpret(hand_tiles: &[Tile], win_tile: Tile, win_method: WinMethod) -> Vec<Vec<Mentsu>> {
-    let i13s = i13s::build(&hand_tiles, win_tile, win_method);
+/// Builds a called mentsu from the tiles written inside a pair of brackets.
+fn to_meld(open: char, tiles: &[Tile]) -> Option<Mentsu> {
+    let mut tiles = tiles.to_vec();
+    tiles.sort();
+
+    let identical = tiles.windows(2).all(|w| w[0] == w[1]);
+
+    // Sets of identical tiles are stored as one tile, which carries the red
+    // five if there is one.
+    if identical && let Some(i) = tiles.iter().position(|t| t.aka) {
+        tiles.swap(0, i);
+    }
+
+    let (kind, call) = match (open, tiles.as_slice()) {
+        ('[', &[t0, t1, t2]) if !identical => {
+            if t0.honor() || t0.add(1) != Some(t1) || t1.add(1) != Some(t2) {
+                return None;
+            }
+            (Kind::Sequence(t0, t1, t2), Call::Chi)
+        }
+        ('[', &[t, _, _]) => (Kind::Triplet(t), Call::Pon),
+        ('[', &[t, _, _, _]) if identical => (Kind::Quad(t), Call::OpenKan),
+        ('(', &[t, _, _, _]) if identical => (Kind::Quad(t), Call::ClosedKan),
+        ('{', &[t, _, _, _]) if identical => (Kind::Quad(t), Call::AddedKan),
+        _ => return None,
+    };
+
+    Some(Mentsu::called(kind, call))
+}
+
+/// Returns every way of reading the concealed tiles as a winning hand, with
+/// the called melds added. Each reading marks the mentsu completed by the
+/// winning tile; a tile that fits several mentsu gives several readings.
+pub fn interpret(
+    hand_tiles: &[Tile],
+    melds: &[Mentsu],
+    win_tile: Tile,
+    win_method: WinMethod,
+) -> Vec<Vec<Mentsu>> {
+    i13s::build(hand_tiles, melds, win_tile, win_method)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn err(s: &str) -> Option<ParseError> {
+        parse(s).err()
+    }
+
+    #[test]
+    fn last_tile_wins() {
+        let hand = parse("123m456m789p11s234s").unwrap();
+        assert_eq!(hand.win_tile, Tile::new(4, Suit::Sou));
+        assert_eq!(hand.win_method, WinMethod::Tsumo);
+
+        let hand = parse("111222333s22m111z").unwrap();
+        assert_eq!(hand.win_tile, Tile::new(1, Suit::Honor));
+        assert_eq!(hand.win_method, WinMethod::Tsumo);
 
-    i13s
+        let hand = parse("123m456m789p11s23s 4s").unwrap();
+        assert_eq!(hand.win_tile, Tile::new(4, Suit::Sou));
+        assert_eq!(hand.win_method, WinMethod::Ron);
+    }
+
+    #[test]
+    fn melds() {
+        let hand = parse("234m5p[678s][777z](1111z) 5p").unwrap();
+        assert_eq!(hand.tiles.len(), 5);
+        assert_eq!(hand.melds.len(), 3);
+        assert_eq!(hand.win_method, WinMethod::Ron);
+    }
+
+    #[test]
+    fn kans_count_as_one_set() {
+        let hand = parse("(1111z)(2222z)(3333z)(4444z)5z 5z").unwrap();
+        assert_eq!(hand.tiles.len(), 2);
+
+        assert_eq!(
+            err("(1111z)345m678p789s11s 1s"),
+            Some(ParseError::TileCount {
+                expected: 11,
+                found: 12
+            })
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(err(""), Some(ParseError::Empty));
+        assert_eq!(err("   "), Some(ParseError::Empty));
+        assert_eq!(
+            err("123x"),
+            Some(ParseError::UnknownSuit {
+                offset: 3,
+                found: 'x'
+            })
+        );
+        assert_eq!(
+            err("123m8z"),
+            Some(ParseError::ValueOutOfRange {
+                offset: 4,
+                value: 8
+            })
+        );
+        assert_eq!(
+            err("123m45"),
+            Some(ParseError::DanglingDigits { offset: 4 })
+        );
+        assert_eq!(err("12 3m"), Some(ParseError::DanglingDigits { offset: 0 }));
+        assert_eq!(
+            err("m123p"),
+            Some(ParseError::SuitWithoutDigits { offset: 0 })
+        );
+        assert_eq!(
+            err("123m]"),
+            Some(ParseError::UnmatchedBracket {
+                offset: 4,
+                found: ']'
+            })
+        );
+        assert_eq!(
+            err("[123m)"),
+            Some(ParseError::UnmatchedBracket {
+                offset: 5,
+                found: ')'
+            })
+        );
+        assert_eq!(
+            err("11z[123m"),
+            Some(ParseError::UnclosedMeld { offset: 3 })
+        );
+        assert_eq!(err("[124m]"), Some(ParseError::InvalidMeld { offset: 0 }));
+        assert_eq!(err("(111z)"), Some(ParseError::InvalidMeld { offset: 0 }));
+        assert_eq!(
+            err("123m 4m"),
+            Some(ParseError::TileCount {
+                expected: 14,
+                found: 4
+            })
+        );
+        assert_eq!(
+            err("123m456m789p11s[555p]"),
+            Some(ParseError::MissingWinTile { offset: 15 })
+        );
+    }
+
+    #[test]
+    fn no_honor_sequences() {
+        assert!(
+            crate::test_util::interpret("234p34s999s555z67z2s")
+                .1
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn too_many_copies() {
+        assert_eq!(
+            err("11111m234p567s22z 2z"),
+            Some(ParseError::TooManyCopies {
+                offset: 4,
+                tile: Tile::new(1, Suit::Man)
+            })
+        );
+
+        // Red fives and called melds count towards the four.
+        assert_eq!(
+            err("234m0p[555p]111z222z 5p"),
+            Some(ParseError::TooManyCopies {
+                offset: 21,
+                tile: Tile::new(5, Suit::Pin)
+            })
+        );
+    }
+
+    #[test]
+    fn one_red_five_per_suit() {
+        assert_eq!(
+            err("000m123p789s11z55z 5z"),
+            Some(ParseError::DuplicateRedFive {
+                offset: 1,
+                tile: Tile::new_aka(Suit::Man)
+            })
+        );
+        assert_eq!(
+            err("234m0p[055p]111z222z 2z"),
+            Some(ParseError::DuplicateRedFive {
+                offset: 7,
+                tile: Tile::new_aka(Suit::Pin)
+            })
+        );
+
+        let hand = parse("406m0p[055s]111z222z 2z").unwrap();
+        assert_eq!(hand.tiles.iter().filter(|t| t.aka).count(), 2);
+        assert!(matches!(hand.melds[0].kind, Kind::Triplet(t) if t.aka));
+    }
 }
@@ -1,4 +1,7 @@
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+use serde::Serialize;
+
+/// How the winning tile was taken.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub enum WinMethod {
     Tsumo,
     Ron,
@@ -1,4 +1,7 @@
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+use serde::Serialize;
+
+/// The shape of the wait the winning tile completed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
 pub enum WinWait {
     Ryanmen,
     Kanchan,
@@ -22,5 +25,3 @@ impl std::fmt::Display for WinWait {
         )
     }
 }
-
-
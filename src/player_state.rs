@@ -1,15 +1,20 @@
-#[derive(Copy, Clone, Debug)]
+//! Who won, relative to the table.
+
+use serde::Serialize;
+
+/// The winner's place at the table.
+#[derive(Copy, Clone, Debug, Serialize)]
 pub struct PlayerState {
     pub seat_wind: Wind,
     pub round_wind: Wind,
     pub dealer: bool,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// A seat or round wind.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
 pub enum Wind {
     East,
     South,
     West,
     North,
 }
-
@@ -0,0 +1,260 @@
+use std::path::Path;
+
+use toml::{Table, Value};
+
+use super::{DoubleWindFu, PRESETS, Ruleset};
+use crate::yaku;
+
+/// Something wrong with the contents of a ruleset file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Issue {
+    UnknownKey(String),
+    UnknownPreset(String),
+    UnknownYaku(String),
+    /// A known key with a value it can't take.
+    InvalidValue {
+        key: String,
+        expected: &'static str,
+    },
+    /// Two options that can't both be set as written.
+    Conflict(&'static str),
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownKey(key) => write!(f, "unknown key `{key}`"),
+            Self::UnknownPreset(name) => write!(
+                f,
+                "unknown preset \"{name}\" (expected one of {})",
+                PRESETS.join(", ")
+            ),
+            Self::UnknownYaku(name) => write!(f, "unknown yaku \"{name}\""),
+            Self::InvalidValue { key, expected } => write!(f, "`{key}` must be {expected}"),
+            Self::Conflict(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+/// Reasons a ruleset file can fail to load.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LoadError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    /// The file parsed, but its contents don't describe a valid ruleset.
+    Invalid(Vec<Issue>),
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Toml(e) => write!(f, "{e}"),
+            Self::Invalid(issues) => {
+                write!(f, "invalid ruleset:")?;
+
+                for issue in issues {
+                    write!(f, "\n  {issue}")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl Ruleset {
+    /// Reads a ruleset from a TOML file. See [`Ruleset::from_toml`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadError> {
+        Self::from_toml(&std::fs::read_to_string(path).map_err(LoadError::Io)?)
+    }
+
+    /// Parses a ruleset such as:
+    ///
+    /// ```toml
+    /// base = "wrc"          # Preset to start from; defaults to Tenhou
+    /// kiriage_mangan = false
+    /// double_wind_fu = 4
+    ///
+    /// [yaku]
+    /// Ippatsu = false       # Disable a yaku by name
+    /// ```
+    ///
+    /// Options that aren't given keep the base preset's value. Every
+    /// problem in the file is reported at once.
+    pub fn from_toml(s: &str) -> Result<Self, LoadError> {
+        let table: Table = s.parse().map_err(LoadError::Toml)?;
+
+        let mut issues = Vec::new();
+
+        let mut rules = match table.get("base") {
+            None => Self::default(),
+            Some(Value::String(name)) => Self::preset(name).unwrap_or_else(|| {
+                issues.push(Issue::UnknownPreset(name.clone()));
+                Self::default()
+            }),
+            Some(_) => {
+                issues.push(invalid("base", "a preset name"));
+                Self::default()
+            }
+        };
+
+        for (key, value) in &table {
+            match key.as_str() {
+                "base" => (),
+                "kiriage_mangan" => set_bool(&mut rules.kiriage_mangan, key, value, &mut issues),
+                "kuitan" => set_bool(&mut rules.kuitan, key, value, &mut issues),
+                "double_yakuman" => set_bool(&mut rules.double_yakuman, key, value, &mut issues),
+                "max_yakuman" => match value.as_integer().map(u32::try_from) {
+                    Some(Ok(n)) if n > 0 => rules.max_yakuman = n,
+                    _ => issues.push(invalid(key, "a positive integer")),
+                },
+                "kazoe_yakuman" => set_bool(&mut rules.kazoe_yakuman, key, value, &mut issues),
+                "multiple_ron" => set_bool(&mut rules.multiple_ron, key, value, &mut issues),
+                "double_wind_fu" => match value.as_integer() {
+                    Some(2) => rules.double_wind_fu = DoubleWindFu::Two,
+                    Some(4) => rules.double_wind_fu = DoubleWindFu::Four,
+                    _ => issues.push(invalid(key, "2 or 4")),
+                },
+                "aka_dora" => match value.as_integer().map(u32::try_from) {
+                    Some(Ok(n)) => rules.aka_dora = n,
+                    _ => issues.push(invalid(key, "a non-negative integer")),
+                },
+                "yaku" => match value.as_table() {
+                    Some(yaku) => set_yaku(&mut rules, yaku, &mut issues),
+                    None => issues.push(invalid(key, "a table of yaku names")),
+                },
+                _ => issues.push(Issue::UnknownKey(key.clone())),
+            }
+        }
+
+        if table
+            .get("kuitan")
+            .is_some_and(|v| v.as_bool() == Some(true))
+            && !rules.yaku_enabled("Tanyao")
+        {
+            issues.push(Issue::Conflict(
+                "`kuitan = true` has no effect while Tanyao is disabled",
+            ));
+        }
+
+        if table
+            .get("double_yakuman")
+            .is_some_and(|v| v.as_bool() == Some(true))
+            && rules.max_yakuman < 2
+        {
+            issues.push(Issue::Conflict(
+                "`double_yakuman = true` has no effect while `max_yakuman` is 1",
+            ));
+        }
+
+        if issues.is_empty() {
+            Ok(rules)
+        } else {
+            Err(LoadError::Invalid(issues))
+        }
+    }
+}
+
+fn invalid(key: &str, expected: &'static str) -> Issue {
+    Issue::InvalidValue {
+        key: key.to_string(),
+        expected,
+    }
+}
+
+fn set_bool(field: &mut bool, key: &str, value: &Value, issues: &mut Vec<Issue>) {
+    match value.as_bool() {
+        Some(b) => *field = b,
+        None => issues.push(invalid(key, "true or false")),
+    }
+}
+
+fn set_yaku(rules: &mut Ruleset, yaku: &Table, issues: &mut Vec<Issue>) {
+    for (name, value) in yaku {
+        let Some(name) = yaku::names().find(|n| n == name) else {
+            issues.push(Issue::UnknownYaku(name.clone()));
+            continue;
+        };
+
+        match value.as_bool() {
+            Some(true) => rules.disabled_yaku.retain(|n| *n != name),
+            Some(false) if rules.yaku_enabled(name) => rules.disabled_yaku.push(name),
+            Some(false) => (),
+            None => issues.push(Issue::InvalidValue {
+                key: format!("yaku.{name}"),
+                expected: "true or false",
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issues(s: &str) -> Vec<Issue> {
+        match Ruleset::from_toml(s) {
+            Err(LoadError::Invalid(issues)) => issues,
+            other => panic!("expected issues, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let mut rules = Ruleset::wrc();
+        rules.max_yakuman = 2;
+        rules.disabled_yaku.push("Ippatsu");
+
+        assert_eq!(Ruleset::from_toml(&rules.to_string()).unwrap(), rules);
+    }
+
+    #[test]
+    fn base_preset() {
+        let rules = Ruleset::from_toml("base = \"wrc\"\nkiriage_mangan = false").unwrap();
+
+        assert_eq!(
+            rules,
+            Ruleset {
+                kiriage_mangan: false,
+                ..Ruleset::wrc()
+            }
+        );
+    }
+
+    #[test]
+    fn max_yakuman() {
+        let rules = Ruleset::from_toml("base = \"wrc\"\nmax_yakuman = 2").unwrap();
+        assert_eq!(rules.max_yakuman, 2);
+
+        assert_eq!(
+            issues("max_yakuman = 0"),
+            [invalid("max_yakuman", "a positive integer")]
+        );
+        assert!(matches!(
+            issues("base = \"wrc\"\ndouble_yakuman = true")[..],
+            [Issue::Conflict(_)]
+        ));
+    }
+
+    #[test]
+    fn every_issue_is_reported() {
+        assert_eq!(
+            issues("base = \"club\"\nkiriage = true\naka_dora = -1\n[yaku]\nNagashi = false"),
+            [
+                Issue::UnknownPreset("club".to_string()),
+                invalid("aka_dora", "a non-negative integer"),
+                Issue::UnknownKey("kiriage".to_string()),
+                Issue::UnknownYaku("Nagashi".to_string()),
+            ]
+        );
+        assert!(matches!(
+            issues("kuitan = true\n[yaku]\nTanyao = false")[..],
+            [Issue::Conflict(_)]
+        ));
+    }
+}
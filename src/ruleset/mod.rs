@@ -0,0 +1,162 @@
+//! Rule variations and the presets that bundle them.
+
+mod load;
+
+pub use load::{Issue, LoadError};
+
+/// Fu for a pair of the player's seat wind when it is also the round wind.
+/// Rule sets disagree on whether the two winds stack.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DoubleWindFu {
+    Two,
+    Four,
+}
+
+impl DoubleWindFu {
+    /// Returns the fu the pair is worth.
+    pub const fn fu(self) -> u32 {
+        match self {
+            Self::Two => 2,
+            Self::Four => 4,
+        }
+    }
+}
+
+/// Optional rules that differ between platforms and tournaments.
+///
+/// Start from a preset and adjust fields, or load a file with
+/// [`Ruleset::load`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Ruleset {
+    /// Rounds 4 han 30 fu and 3 han 60 fu up to mangan.
+    pub kiriage_mangan: bool,
+    /// Allows Tanyao in an open hand.
+    pub kuitan: bool,
+    /// Scores double yakuman variants (e.g. Suuankou tanki) as two yakuman.
+    pub double_yakuman: bool,
+    /// The most yakuman a hand can be worth, counting double yakuman as
+    /// two. 1 means yakuman never stack.
+    pub max_yakuman: u32,
+    /// Scores 13+ han as yakuman rather than capping it at sanbaiman.
+    pub kazoe_yakuman: bool,
+    pub double_wind_fu: DoubleWindFu,
+    /// Number of red fives in the wall.
+    pub aka_dora: u32,
+    /// Lets several players ron the same discard. Otherwise only the first
+    /// player in turn order wins (atamahane). See
+    /// [`settle_ron`](crate::score::settle_ron).
+    pub multiple_ron: bool,
+    /// Names of yaku that are never scored.
+    pub disabled_yaku: Vec<&'static str>,
+}
+
+/// Names accepted by [`Ruleset::preset`].
+pub const PRESETS: &[&str] = &["tenhou", "mahjong-soul", "wrc", "ema"];
+
+impl Ruleset {
+    /// Looks up a named preset, e.g. `"tenhou"`. See [`PRESETS`].
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "tenhou" => Some(Self::tenhou()),
+            "mahjong-soul" => Some(Self::mahjong_soul()),
+            "wrc" => Some(Self::wrc()),
+            "ema" => Some(Self::ema()),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` unless the yaku has been disabled.
+    pub fn yaku_enabled(&self, name: &str) -> bool {
+        !self.disabled_yaku.contains(&name)
+    }
+
+    pub const fn tenhou() -> Self {
+        Self {
+            kiriage_mangan: false,
+            kuitan: true,
+            double_yakuman: false,
+            max_yakuman: 6,
+            kazoe_yakuman: true,
+            double_wind_fu: DoubleWindFu::Four,
+            aka_dora: 3,
+            multiple_ron: true,
+            disabled_yaku: Vec::new(),
+        }
+    }
+
+    pub const fn mahjong_soul() -> Self {
+        Self {
+            kiriage_mangan: false,
+            kuitan: true,
+            double_yakuman: true,
+            max_yakuman: 6,
+            kazoe_yakuman: true,
+            double_wind_fu: DoubleWindFu::Four,
+            aka_dora: 3,
+            multiple_ron: true,
+            disabled_yaku: Vec::new(),
+        }
+    }
+
+    /// World Riichi Championship rules.
+    pub const fn wrc() -> Self {
+        Self {
+            kiriage_mangan: true,
+            kuitan: true,
+            double_yakuman: false,
+            max_yakuman: 1,
+            kazoe_yakuman: false,
+            double_wind_fu: DoubleWindFu::Two,
+            aka_dora: 0,
+            multiple_ron: false,
+            disabled_yaku: Vec::new(),
+        }
+    }
+
+    /// European Mahjong Association rules.
+    pub const fn ema() -> Self {
+        Self {
+            kiriage_mangan: false,
+            kuitan: true,
+            double_yakuman: false,
+            max_yakuman: 1,
+            kazoe_yakuman: false,
+            double_wind_fu: DoubleWindFu::Four,
+            aka_dora: 0,
+            multiple_ron: true,
+            disabled_yaku: Vec::new(),
+        }
+    }
+}
+
+/// Writes the ruleset in the same TOML format that [`Ruleset::from_toml`]
+/// reads, with every option spelled out.
+impl std::fmt::Display for Ruleset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "kiriage_mangan = {}", self.kiriage_mangan)?;
+        writeln!(f, "kuitan = {}", self.kuitan)?;
+        writeln!(f, "double_yakuman = {}", self.double_yakuman)?;
+        writeln!(f, "max_yakuman = {}", self.max_yakuman)?;
+        writeln!(f, "kazoe_yakuman = {}", self.kazoe_yakuman)?;
+        writeln!(f, "double_wind_fu = {}", self.double_wind_fu.fu())?;
+        writeln!(f, "aka_dora = {}", self.aka_dora)?;
+        writeln!(f, "multiple_ron = {}", self.multiple_ron)?;
+
+        if !self.disabled_yaku.is_empty() {
+            writeln!(f, "\n[yaku]")?;
+
+            for name in &self.disabled_yaku {
+                writeln!(f, "{name:?} = false")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self::tenhou()
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{mentsu, parser::Hand, ruleset::Ruleset, win_context::WinContext};
+use serde::Serialize;
+
+/// Han from dora. These add to a hand's value but don't count as yaku.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct Dora {
+    pub dora: u32,
+    pub ura: u32,
+    pub aka: u32,
+}
+
+impl Dora {
+    pub const fn total(self) -> u32 {
+        self.dora + self.ura + self.aka
+    }
+}
+
+/// Counts the dora in a hand, including its called melds.
+///
+/// Each indicator counts separately, so a tile indicated twice is worth 2
+/// han per copy. Ura-dora only count after riichi, and red fives beyond the
+/// ruleset's `aka_dora` count are ignored.
+pub fn count_dora(hand: &Hand, ctx: WinContext, rules: &Ruleset) -> Dora {
+    let mut tiles = hand.tiles.clone();
+    tiles.extend(mentsu::get_tiles(&hand.melds));
+
+    let count = |indicators: &[crate::tile::Tile]| -> u32 {
+        indicators
+            .iter()
+            .map(|i| i.dora_from_indicator())
+            .map(|d| tiles.iter().filter(|&&t| t == d).count() as u32)
+            .sum()
+    };
+
+    Dora {
+        dora: count(&hand.dora),
+        ura: if ctx.any_riichi() {
+            count(&hand.ura_dora)
+        } else {
+            0
+        },
+        aka: (tiles.iter().filter(|t| t.aka).count() as u32).min(rules.aka_dora),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn dora_of(s: &str, dora: &str, ura: &str, rules: &Ruleset) -> Dora {
+        let mut hand = parser::parse(s).unwrap();
+        hand.dora = parser::parse_tiles(dora).unwrap();
+        hand.ura_dora = parser::parse_tiles(ura).unwrap();
+
+        let riichi = WinContext {
+            riichi: true,
+            ..Default::default()
+        };
+
+        count_dora(&hand, riichi, rules)
+    }
+
+    #[test]
+    fn indicators() {
+        let rules = Ruleset::default();
+        let hand = "234m067p[999s]11z22z 2z";
+
+        // 1m points at 2m, 8s at each 9s in the pon, 1z at the 2z pair.
+        assert_eq!(dora_of(hand, "1m8s", "", &rules).dora, 4);
+        assert_eq!(dora_of(hand, "1z1z", "", &rules).dora, 6);
+        assert_eq!(dora_of(hand, "", "4z", &rules).ura, 2);
+        assert_eq!(dora_of(hand, "", "", &rules).aka, 1);
+        assert_eq!(dora_of(hand, "", "", &Ruleset::wrc()).aka, 0);
+    }
+
+    #[test]
+    fn red_fives() {
+        // The red five in the pon counts once, not once per copy.
+        let hand = "234m067p[055s]11z22z 2z";
+
+        assert_eq!(dora_of(hand, "", "", &Ruleset::tenhou()).aka, 2);
+        assert_eq!(dora_of(hand, "", "", &Ruleset::wrc()).aka, 0);
+        assert_eq!(dora_of(hand, "4s", "", &Ruleset::tenhou()).dora, 3);
+    }
+
+    #[test]
+    fn ura_needs_riichi() {
+        let mut hand = parser::parse("234m067p[999s]11z22z 2z").unwrap();
+        hand.ura_dora = parser::parse_tiles("4z").unwrap();
+
+        let rules = Ruleset::default();
+        assert_eq!(count_dora(&hand, WinContext::default(), &rules).ura, 0);
+
+        let double_riichi = WinContext {
+            double_riichi: true,
+            ..Default::default()
+        };
+        assert_eq!(count_dora(&hand, double_riichi, &rules).ura, 2);
+    }
+}
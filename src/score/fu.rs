@@ -0,0 +1,291 @@
+use crate::{
+    mentsu::{self, Mentsu, kind::Kind},
+    parser::{WinMethod, WinWait},
+    player_state::PlayerState,
+    ruleset::Ruleset,
+};
+use serde::Serialize;
+
+/// Where a fu contribution came from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+pub enum FuSource {
+    /// Fuutei, awarded to every hand.
+    Base,
+    /// Won by ron with a closed hand.
+    MenzenRon,
+    Tsumo,
+    /// A triplet or quad.
+    Set(Mentsu),
+    /// A pair of dragons or value winds.
+    Pair(Mentsu),
+    /// A kanchan, penchan or tanki wait.
+    Wait(WinWait),
+    /// Brings an open hand with no other fu up to the 30 fu minimum.
+    OpenPinfu,
+    /// Seven pairs are always worth exactly 25 fu.
+    Chiitoitsu,
+}
+
+impl std::fmt::Display for FuSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Base => write!(f, "Base"),
+            Self::MenzenRon => write!(f, "Menzen ron"),
+            Self::Tsumo => write!(f, "Tsumo"),
+            Self::Set(m) => write!(
+                f,
+                "{} {} {} {}",
+                if m.open { "Open" } else { "Closed" },
+                if m.honor() || m.entirely_terminal() {
+                    "terminal/honor"
+                } else {
+                    "simple"
+                },
+                if m.quad() { "quad" } else { "triplet" },
+                m.kind
+            ),
+            Self::Pair(m) => write!(f, "Value pair {}", m.kind),
+            Self::Wait(wait) => write!(f, "{wait} wait"),
+            Self::OpenPinfu => write!(f, "Open pinfu minimum"),
+            Self::Chiitoitsu => write!(f, "Chiitoitsu"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+pub struct FuItem {
+    pub source: FuSource,
+    pub fu: u32,
+}
+
+/// Every fu contribution to a hand, in scoring order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FuBreakdown {
+    pub items: Vec<FuItem>,
+}
+
+impl FuBreakdown {
+    fn push(&mut self, source: FuSource, fu: u32) {
+        self.items.push(FuItem { source, fu });
+    }
+
+    /// Returns the sum of all contributions, before rounding.
+    pub fn raw(&self) -> u32 {
+        self.items.iter().map(|i| i.fu).sum()
+    }
+
+    /// Returns the hand's fu, rounded up to the nearest 10. Chiitoitsu's
+    /// 25 fu is never rounded.
+    pub fn total(&self) -> u32 {
+        if self.items.iter().any(|i| i.source == FuSource::Chiitoitsu) {
+            return self.raw();
+        }
+
+        self.raw().div_ceil(10) * 10
+    }
+
+    /// Returns the fixed breakdown for a seven pairs hand.
+    pub fn chiitoitsu() -> Self {
+        let mut breakdown = Self { items: Vec::new() };
+        breakdown.push(FuSource::Chiitoitsu, 25);
+        breakdown
+    }
+}
+
+impl std::fmt::Display for FuBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for item in &self.items {
+            writeln!(f, "{:>3}  {}", item.fu, item.source)?;
+        }
+
+        if self.total() == self.raw() {
+            write!(f, "{:>3}  Total", self.total())
+        } else {
+            write!(f, "{:>3}  Total ({} rounded up)", self.total(), self.raw())
+        }
+    }
+}
+
+/// Itemizes the hand's fu.
+pub fn fu_breakdown(
+    hand: &[Mentsu],
+    win_method: WinMethod,
+    player: PlayerState,
+    rules: &Ruleset,
+) -> FuBreakdown {
+    if hand.len() == 7 && hand.iter().all(|m| m.pair()) {
+        return FuBreakdown::chiitoitsu();
+    }
+
+    let menzenchin = mentsu::menzenchin(hand);
+
+    let mut breakdown = FuBreakdown { items: Vec::new() };
+
+    breakdown.push(FuSource::Base, 20);
+
+    match (win_method, menzenchin) {
+        (WinMethod::Ron, true) => breakdown.push(FuSource::MenzenRon, 10),
+        (WinMethod::Tsumo, _) => breakdown.push(FuSource::Tsumo, 2),
+        _ => (),
+    }
+
+    for m in hand {
+        let mut pts = match m.kind {
+            Kind::Triplet(_) => 4,
+            Kind::Quad(_) => 16,
+            _ => continue,
+        };
+
+        if m.open {
+            pts /= 2;
+        }
+
+        if m.honor() || m.entirely_terminal() {
+            pts *= 2;
+        }
+
+        breakdown.push(FuSource::Set(*m), pts);
+    }
+
+    for m in hand.iter().filter(|m| m.pair()) {
+        let pts = pair_fu(*m, player, rules);
+
+        if pts > 0 {
+            breakdown.push(FuSource::Pair(*m), pts);
+        }
+    }
+
+    for m in hand {
+        if let Some(wait) = m.win_wait
+            && !matches!(wait, WinWait::Ryanmen | WinWait::Shanpon)
+        {
+            breakdown.push(FuSource::Wait(wait), 2);
+        }
+    }
+
+    let ryanmen = hand
+        .iter()
+        .any(|m| m.win_wait.is_some_and(|w| w == WinWait::Ryanmen));
+
+    match (win_method, menzenchin, breakdown.raw()) {
+        // Pinfu tsumo is a flat 20 fu: the tsumo fu is waived.
+        (WinMethod::Tsumo, true, 22) if ryanmen => {
+            breakdown.items.retain(|i| i.source != FuSource::Tsumo);
+        }
+        (_, false, 20) => breakdown.push(FuSource::OpenPinfu, 10),
+        _ => (),
+    }
+
+    breakdown
+}
+
+/// Computes the hand's fu.
+pub fn fu(hand: &[Mentsu], win_method: WinMethod, player: PlayerState, rules: &Ruleset) -> u32 {
+    fu_breakdown(hand, win_method, player, rules).total()
+}
+
+/// Returns the fu for a pair: 2 for dragons, seat wind or round wind.
+fn pair_fu(pair: Mentsu, player: PlayerState, rules: &Ruleset) -> u32 {
+    let Kind::Pair(t) = pair.kind else {
+        return 0;
+    };
+
+    if t.dragon() {
+        return 2;
+    }
+
+    match t.wind() {
+        Some(w) if w == player.seat_wind && w == player.round_wind => rules.double_wind_fu.fu(),
+        Some(w) if w == player.seat_wind || w == player.round_wind => 2,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{self, DEALER, NON_DEALER};
+
+    /// Fu of every interpretation of `s`, lowest first.
+    fn fu_of(s: &str) -> Vec<u32> {
+        fu_with(s, NON_DEALER, &Ruleset::default())
+    }
+
+    fn fu_with(s: &str, player: PlayerState, rules: &Ruleset) -> Vec<u32> {
+        let (hand, i13s) = test_util::interpret(s);
+
+        let mut fu: Vec<u32> = i13s
+            .iter()
+            .map(|m| fu(m, hand.win_method, player, rules))
+            .collect();
+
+        fu.sort();
+        fu
+    }
+
+    #[test]
+    fn pinfu() {
+        assert_eq!(fu_of("123m456m789p11s23s4s"), [20]);
+        assert_eq!(fu_of("123m456m789p11s23s 4s"), [30]);
+    }
+
+    #[test]
+    fn tsumo_with_other_fu() {
+        // Kanchan tsumo keeps its tsumo fu: 20 + 2 + 2, rounded up.
+        assert_eq!(fu_of("123m456m789p11s24s3s"), [30]);
+    }
+
+    #[test]
+    fn open_hand_minimum() {
+        assert_eq!(fu_of("[123m]456m789p11s23s 4s"), [30]);
+        assert_eq!(fu_of("[123m]456m789p11s23s4s"), [30]);
+    }
+
+    #[test]
+    fn chiitoitsu() {
+        assert_eq!(fu_of("1122m3344p5566s77z"), [25]);
+        assert_eq!(fu_of("1122m3344p5566s7z 7z"), [25]);
+    }
+
+    #[test]
+    fn sets_and_waits() {
+        // 30 for a closed ron, 8 for the closed honor triplet, 2 for tanki.
+        assert_eq!(fu_of("123m456m789p111z5s 5s"), [40]);
+    }
+
+    #[test]
+    fn kans() {
+        // Ron on a tanki wait (2 fu) in each, so only the kan differs.
+        assert_eq!(fu_of("[2222m]345m678p789s1s 1s"), [30]); // 20 + 8 + 2
+        assert_eq!(fu_of("[1111z]345m678p789s1s 1s"), [40]); // 20 + 16 + 2
+        assert_eq!(fu_of("(2222m)345m678p789s1s 1s"), [50]); // 30 + 16 + 2
+        assert_eq!(fu_of("(1111z)345m678p789s1s 1s"), [70]); // 30 + 32 + 2
+    }
+
+    #[test]
+    fn value_pairs() {
+        let pair_fu = |s, player, rules: &Ruleset| {
+            let (hand, i13s) = test_util::interpret(s);
+
+            fu_breakdown(&i13s[0], hand.win_method, player, rules)
+                .items
+                .iter()
+                .filter(|item| matches!(item.source, FuSource::Pair(_)))
+                .map(|item| item.fu)
+                .sum::<u32>()
+        };
+
+        // Tenhou scores a double wind pair as 4 fu, WRC as 2.
+        let (tenhou, wrc) = (Ruleset::tenhou(), Ruleset::wrc());
+
+        let east = "234m567p678s11z23s 4s";
+        assert_eq!(pair_fu(east, DEALER, &tenhou), 4);
+        assert_eq!(pair_fu(east, DEALER, &wrc), 2);
+        assert_eq!(pair_fu(east, NON_DEALER, &tenhou), 2);
+
+        assert_eq!(pair_fu("234m567p678s22z23s 4s", NON_DEALER, &tenhou), 2);
+        assert_eq!(pair_fu("234m567p678s33z23s 4s", NON_DEALER, &tenhou), 0);
+        assert_eq!(pair_fu("234m567p678s55z23s 4s", NON_DEALER, &tenhou), 2);
+        assert_eq!(pair_fu("234m567p678s55s23s 4s", NON_DEALER, &tenhou), 0);
+    }
+}
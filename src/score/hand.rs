@@ -0,0 +1,307 @@
+use std::cmp::Reverse;
+
+use serde::{Serialize, Serializer, ser::SerializeStruct};
+
+use super::{Dora, FuBreakdown, Payout, count_dora, fu_breakdown, payment, yakuman_payment};
+use crate::{
+    mentsu::{self, Mentsu},
+    parser::{self, Hand},
+    player_state::PlayerState,
+    ruleset::Ruleset,
+    table_state::TableState,
+    win_context::WinContext,
+    yaku::{self, Yaku},
+};
+
+/// One interpretation of a hand, scored on its own.
+#[derive(Debug, Clone)]
+pub struct HandScore {
+    pub mentsu: Vec<Mentsu>,
+    pub yaku: Vec<&'static Yaku>,
+    /// Han from yaku and dora, with open hands' reductions applied.
+    pub han: u32,
+    /// Total yakuman, counting double yakuman twice where the rules allow,
+    /// up to [`Ruleset::max_yakuman`].
+    pub yakuman: u32,
+    pub dora: Dora,
+    pub fu: FuBreakdown,
+    /// `None` if the interpretation has no yaku and so can't win.
+    pub payout: Option<Payout>,
+}
+
+impl HandScore {
+    /// Orders interpretations by what they pay, then by han, then by fu.
+    fn rank(&self) -> (bool, u32, u32, u32) {
+        (
+            self.payout.is_some(),
+            self.payout.map_or(0, |p| p.payment.total()),
+            self.han,
+            self.fu.total(),
+        )
+    }
+}
+
+// Yaku can't derive `Serialize` since they hold their predicate, and their
+// han depends on the hand, so each is written out as its name and han.
+impl Serialize for HandScore {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct YakuHan {
+            name: &'static str,
+            han: u32,
+            yakuman: bool,
+        }
+
+        let menzenchin = mentsu::menzenchin(&self.mentsu);
+        let yaku: Vec<YakuHan> = self
+            .yaku
+            .iter()
+            .map(|y| YakuHan {
+                name: y.name,
+                han: y.han(menzenchin),
+                yakuman: y.yakuman(),
+            })
+            .collect();
+
+        let mut s = serializer.serialize_struct("HandScore", 8)?;
+        s.serialize_field("mentsu", &self.mentsu)?;
+        s.serialize_field("yaku", &yaku)?;
+        s.serialize_field("han", &self.han)?;
+        s.serialize_field("yakuman", &self.yakuman)?;
+        s.serialize_field("dora", &self.dora)?;
+        s.serialize_field("fu", &self.fu.total())?;
+        s.serialize_field("fu_breakdown", &self.fu.items)?;
+        s.serialize_field("payout", &self.payout)?;
+        s.end()
+    }
+}
+
+/// Every interpretation of a hand, best first.
+#[derive(Debug, Clone)]
+pub struct Scores {
+    pub ranked: Vec<HandScore>,
+}
+
+impl Scores {
+    /// Returns the highest-paying interpretation, or `None` if no
+    /// interpretation has a yaku.
+    pub fn best(&self) -> Option<&HandScore> {
+        self.ranked.first().filter(|s| s.payout.is_some())
+    }
+}
+
+impl Serialize for Scores {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Scores", 2)?;
+        s.serialize_field("best", &self.best())?;
+        s.serialize_field("ranked", &self.ranked)?;
+        s.end()
+    }
+}
+
+/// Scores every interpretation of `hand` and ranks them.
+pub fn score_hand(
+    hand: &Hand,
+    player: PlayerState,
+    table: TableState,
+    ctx: WinContext,
+    rules: &Ruleset,
+) -> Scores {
+    let dora = count_dora(hand, ctx, rules);
+
+    let mut ranked: Vec<HandScore> =
+        parser::interpret(&hand.tiles, &hand.melds, hand.win_tile, hand.win_method)
+            .into_iter()
+            .map(|m| score_interpretation(m, hand, dora, player, table, ctx, rules))
+            .collect();
+
+    ranked.sort_by_key(|s| Reverse(s.rank()));
+
+    Scores { ranked }
+}
+
+fn score_interpretation(
+    mentsu: Vec<Mentsu>,
+    hand: &Hand,
+    dora: Dora,
+    player: PlayerState,
+    table: TableState,
+    ctx: WinContext,
+    rules: &Ruleset,
+) -> HandScore {
+    let yaku = yaku::find(&mentsu, player, ctx, rules);
+    let menzenchin = mentsu::menzenchin(&mentsu);
+    let fu = fu_breakdown(&mentsu, hand.win_method, player, rules);
+
+    let mut han: u32 = yaku.iter().map(|y| y.han(menzenchin)).sum();
+    let yakuman = yaku
+        .iter()
+        .map(|y| y.yakuman_count(rules))
+        .sum::<u32>()
+        .min(rules.max_yakuman);
+
+    // Dora only add to a hand that already has a yaku, and never to a
+    // yakuman.
+    let dora = if yaku.is_empty() || yakuman > 0 {
+        Dora::default()
+    } else {
+        dora
+    };
+    han += dora.total();
+
+    let payout = match (yaku.is_empty(), yakuman) {
+        (true, _) => None,
+        (false, 0) => Some(payment(
+            han,
+            fu.total(),
+            hand.win_method,
+            player,
+            table,
+            rules,
+        )),
+        (false, n) => Some(yakuman_payment(n, hand.win_method, player, table)),
+    };
+
+    HandScore {
+        mentsu,
+        yaku,
+        han,
+        yakuman,
+        dora,
+        fu,
+        payout,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::NON_DEALER;
+
+    fn score(s: &str, dora: &str, ctx: WinContext) -> Scores {
+        let mut hand = parser::parse(s).unwrap();
+        hand.dora = parser::parse_tiles(dora).unwrap();
+
+        score_hand(
+            &hand,
+            NON_DEALER,
+            TableState::default(),
+            ctx,
+            &Ruleset::default(),
+        )
+    }
+
+    fn names(score: &HandScore) -> Vec<&'static str> {
+        score.yaku.iter().map(|y| y.name).collect()
+    }
+
+    #[test]
+    fn ranked_by_payment() {
+        // Three triplets (Sanankou, 2 han) or three runs (Iipeikou, 1 han).
+        let scores = score("222333444m67p11z 8p", "", WinContext::default());
+
+        assert_eq!(scores.ranked.len(), 2);
+        assert_eq!(names(scores.best().unwrap()), ["Sanankou"]);
+        assert_eq!(names(&scores.ranked[1]), ["Iipeikou"]);
+    }
+
+    #[test]
+    fn ties_broken_by_han_then_fu() {
+        let riichi = WinContext {
+            riichi: true,
+            ..Default::default()
+        };
+
+        // Six dora bring both interpretations to baiman.
+        let scores = score("222333444m67p11z 8p", "1m3m", riichi);
+        let [first, second] = &scores.ranked[..] else {
+            panic!("expected two interpretations");
+        };
+
+        assert_eq!(
+            first.payout.unwrap().payment,
+            second.payout.unwrap().payment
+        );
+        assert_eq!((first.han, second.han), (9, 8));
+
+        // Riichi, tsumo and three dora make mangan whether the 4m finished
+        // the run or the pair, but only the tanki wait earns fu.
+        let scores = score("2344m999p456s789s4m", "8p", riichi);
+        let [first, second] = &scores.ranked[..] else {
+            panic!("expected two interpretations");
+        };
+
+        assert_eq!(
+            first.payout.unwrap().payment,
+            second.payout.unwrap().payment
+        );
+        assert_eq!(first.han, second.han);
+        assert_eq!((first.fu.total(), second.fu.total()), (40, 30));
+    }
+
+    #[test]
+    fn best_needs_a_yaku() {
+        let scores = score("123m456p789s11z23s 4s", "", WinContext::default());
+
+        assert!(scores.best().is_none());
+        assert!(scores.ranked.iter().all(|s| s.payout.is_none()));
+    }
+
+    #[test]
+    fn json_field_names() {
+        let scores = score("123m456m789p11s23s 4s", "", WinContext::default());
+        let json = serde_json::to_value(&scores).unwrap();
+        let keys = |v: &serde_json::Value| -> Vec<String> {
+            v.as_object().unwrap().keys().cloned().collect()
+        };
+
+        assert_eq!(keys(&json), ["best", "ranked"]);
+        assert_eq!(
+            keys(&json["best"]),
+            [
+                "dora",
+                "fu",
+                "fu_breakdown",
+                "han",
+                "mentsu",
+                "payout",
+                "yaku",
+                "yakuman"
+            ]
+        );
+        assert_eq!(
+            json["best"]["yaku"][0],
+            serde_json::json!({ "name": "Pinfu", "han": 1, "yakuman": false })
+        );
+        assert_eq!(json["best"]["fu"], 30);
+        assert_eq!(json["best"]["payout"]["payment"]["Ron"], 1000);
+    }
+
+    fn yakuman(s: &str, rules: &Ruleset) -> u32 {
+        let hand = parser::parse(s).unwrap();
+        let scores = score_hand(
+            &hand,
+            NON_DEALER,
+            TableState::default(),
+            WinContext::default(),
+            rules,
+        );
+
+        scores.best().unwrap().yakuman
+    }
+
+    #[test]
+    fn yakuman_stack_up_to_the_cap() {
+        // Daisuushii, tsuuiisou and suuankou tanki.
+        let hand = "111222333444z5z 5z";
+
+        assert_eq!(yakuman(hand, &Ruleset::tenhou()), 3);
+        assert_eq!(yakuman(hand, &Ruleset::mahjong_soul()), 5);
+        assert_eq!(yakuman(hand, &Ruleset::wrc()), 1);
+        assert_eq!(yakuman(hand, &Ruleset::ema()), 1);
+
+        let mut rules = Ruleset::mahjong_soul();
+        rules.max_yakuman = 2;
+        assert_eq!(yakuman(hand, &rules), 2);
+    }
+}
@@ -0,0 +1,11 @@
+//! Fu, dora and payments, and [`score_hand`] to put them together.
+
+mod dora;
+mod fu;
+mod hand;
+mod payment;
+
+pub use dora::{Dora, count_dora};
+pub use fu::{FuBreakdown, FuItem, FuSource, fu, fu_breakdown};
+pub use hand::{HandScore, Scores, score_hand};
+pub use payment::{Limit, Payment, Payout, payment, settle_ron, yakuman_payment};
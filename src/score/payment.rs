@@ -0,0 +1,362 @@
+use crate::{
+    parser::WinMethod, player_state::PlayerState, ruleset::Ruleset, table_state::TableState,
+};
+use serde::Serialize;
+
+/// Limit hand tiers, which fix the base points regardless of fu.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum Limit {
+    Mangan,
+    Haneman,
+    Baiman,
+    Sanbaiman,
+    /// 13 or more han without a yakuman yaku.
+    KazoeYakuman,
+    Yakuman,
+}
+
+impl std::fmt::Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Mangan => "Mangan",
+                Self::Haneman => "Haneman",
+                Self::Baiman => "Baiman",
+                Self::Sanbaiman => "Sanbaiman",
+                Self::KazoeYakuman => "Kazoe yakuman",
+                Self::Yakuman => "Yakuman",
+            }
+        )
+    }
+}
+
+/// Points owed to the winner, including honba.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+pub enum Payment {
+    /// Paid in full by the discarder.
+    Ron(u32),
+    /// Dealer tsumo: every other player pays this amount.
+    TsumoAll(u32),
+    /// Non-dealer tsumo.
+    TsumoSplit { dealer: u32, non_dealer: u32 },
+}
+
+impl Payment {
+    /// Returns the total points collected by the winner.
+    pub const fn total(self) -> u32 {
+        match self {
+            Self::Ron(p) => p,
+            Self::TsumoAll(p) => p * 3,
+            Self::TsumoSplit { dealer, non_dealer } => dealer + non_dealer * 2,
+        }
+    }
+}
+
+impl std::fmt::Display for Payment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ron(p) => write!(f, "Ron {p}"),
+            Self::TsumoAll(p) => write!(f, "Tsumo {p} all"),
+            Self::TsumoSplit { dealer, non_dealer } => write!(f, "Tsumo {non_dealer}/{dealer}"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+pub struct Payout {
+    /// Base points before the dealer/non-dealer multipliers.
+    pub base: u32,
+    pub limit: Option<Limit>,
+    pub payment: Payment,
+    /// Riichi deposits collected from the table.
+    pub deposits: u32,
+}
+
+impl Payout {
+    /// Returns everything the winner gains: payments plus deposits.
+    pub const fn total(self) -> u32 {
+        self.payment.total() + self.deposits
+    }
+}
+
+/// Computes the payment for a hand worth `han` and `fu`.
+///
+/// 13 or more han is counted as kazoe yakuman if `rules` allow it, and as
+/// sanbaiman otherwise. The caller is responsible for checking that the hand
+/// has a yaku at all.
+pub fn payment(
+    han: u32,
+    fu: u32,
+    win_method: WinMethod,
+    player: PlayerState,
+    table: TableState,
+    rules: &Ruleset,
+) -> Payout {
+    let limit = match han {
+        13.. if rules.kazoe_yakuman => Some(Limit::KazoeYakuman),
+        11.. => Some(Limit::Sanbaiman),
+        8..=10 => Some(Limit::Baiman),
+        6..=7 => Some(Limit::Haneman),
+        5 => Some(Limit::Mangan),
+        _ => None,
+    };
+
+    let (base, limit) = match limit {
+        Some(limit) => (limit_base(limit), Some(limit)),
+        None => match fu * 2u32.pow(han + 2) {
+            // Anything over the mangan cap is a mangan.
+            base if base > 2000 => (2000, Some(Limit::Mangan)),
+            // Kiriage: 4 han 30 fu and 3 han 60 fu round up to mangan.
+            1920 if rules.kiriage_mangan => (2000, Some(Limit::Mangan)),
+            base => (base, None),
+        },
+    };
+
+    from_base(base, limit, win_method, player, table)
+}
+
+/// Computes the payment for a hand with `count` yakuman.
+pub fn yakuman_payment(
+    count: u32,
+    win_method: WinMethod,
+    player: PlayerState,
+    table: TableState,
+) -> Payout {
+    from_base(
+        limit_base(Limit::Yakuman) * count,
+        Some(Limit::Yakuman),
+        win_method,
+        player,
+        table,
+    )
+}
+
+/// Settles a discard that several players called ron on. `payouts` are
+/// each claimant's payout as if they had won alone, in turn order from the
+/// discarder.
+///
+/// With `rules.multiple_ron` every claimant wins, each with their own
+/// honba, and the riichi deposits go to the first. Otherwise only the first
+/// wins (atamahane).
+pub fn settle_ron(payouts: &[Payout], rules: &Ruleset) -> Vec<Payout> {
+    if !rules.multiple_ron {
+        return payouts.iter().take(1).copied().collect();
+    }
+
+    payouts
+        .iter()
+        .enumerate()
+        .map(|(i, &payout)| Payout {
+            deposits: if i == 0 { payout.deposits } else { 0 },
+            ..payout
+        })
+        .collect()
+}
+
+const fn limit_base(limit: Limit) -> u32 {
+    match limit {
+        Limit::Mangan => 2000,
+        Limit::Haneman => 3000,
+        Limit::Baiman => 4000,
+        Limit::Sanbaiman => 6000,
+        Limit::KazoeYakuman | Limit::Yakuman => 8000,
+    }
+}
+
+fn from_base(
+    base: u32,
+    limit: Option<Limit>,
+    win_method: WinMethod,
+    player: PlayerState,
+    table: TableState,
+) -> Payout {
+    // 300 per honba, paid by the discarder or split between all three.
+    let honba = table.honba * 100;
+
+    let payment = match (win_method, player.dealer) {
+        (WinMethod::Ron, true) => Payment::Ron(round_up(base * 6) + honba * 3),
+        (WinMethod::Ron, false) => Payment::Ron(round_up(base * 4) + honba * 3),
+        (WinMethod::Tsumo, true) => Payment::TsumoAll(round_up(base * 2) + honba),
+        (WinMethod::Tsumo, false) => Payment::TsumoSplit {
+            dealer: round_up(base * 2) + honba,
+            non_dealer: round_up(base) + honba,
+        },
+    };
+
+    Payout {
+        base,
+        limit,
+        payment,
+        deposits: table.riichi_sticks * 1000,
+    }
+}
+
+/// Rounds up to the nearest 100.
+const fn round_up(points: u32) -> u32 {
+    points.div_ceil(100) * 100
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{DEALER, NON_DEALER};
+
+    const TABLE: TableState = TableState {
+        honba: 0,
+        riichi_sticks: 0,
+    };
+
+    fn ron(han: u32, fu: u32, player: PlayerState, rules: &Ruleset) -> Payout {
+        payment(han, fu, WinMethod::Ron, player, TABLE, rules)
+    }
+
+    #[test]
+    fn below_mangan() {
+        let rules = Ruleset::tenhou();
+
+        assert_eq!(ron(1, 30, NON_DEALER, &rules).payment, Payment::Ron(1000));
+        assert_eq!(ron(2, 30, NON_DEALER, &rules).payment, Payment::Ron(2000));
+        assert_eq!(ron(3, 30, NON_DEALER, &rules).payment, Payment::Ron(3900));
+        assert_eq!(ron(3, 30, DEALER, &rules).payment, Payment::Ron(5800));
+        assert_eq!(ron(1, 30, NON_DEALER, &rules).limit, None);
+    }
+
+    #[test]
+    fn limits() {
+        let rules = Ruleset::tenhou();
+        let limit = |han, fu| {
+            let payout = ron(han, fu, NON_DEALER, &rules);
+            (payout.limit, payout.payment)
+        };
+
+        assert_eq!(limit(4, 40), (Some(Limit::Mangan), Payment::Ron(8000)));
+        assert_eq!(limit(5, 30), (Some(Limit::Mangan), Payment::Ron(8000)));
+        assert_eq!(limit(6, 30), (Some(Limit::Haneman), Payment::Ron(12000)));
+        assert_eq!(limit(7, 30), (Some(Limit::Haneman), Payment::Ron(12000)));
+        assert_eq!(limit(8, 30), (Some(Limit::Baiman), Payment::Ron(16000)));
+        assert_eq!(limit(10, 30), (Some(Limit::Baiman), Payment::Ron(16000)));
+        assert_eq!(limit(11, 30), (Some(Limit::Sanbaiman), Payment::Ron(24000)));
+        assert_eq!(
+            limit(13, 30),
+            (Some(Limit::KazoeYakuman), Payment::Ron(32000))
+        );
+
+        assert_eq!(ron(5, 30, DEALER, &rules).payment, Payment::Ron(12000));
+    }
+
+    #[test]
+    fn kazoe_yakuman_rule() {
+        let payout = ron(13, 30, NON_DEALER, &Ruleset::wrc());
+
+        assert_eq!(payout.limit, Some(Limit::Sanbaiman));
+        assert_eq!(payout.payment, Payment::Ron(24000));
+    }
+
+    #[test]
+    fn kiriage() {
+        let tenhou = Ruleset::tenhou();
+        let wrc = Ruleset::wrc();
+
+        assert_eq!(ron(4, 30, NON_DEALER, &tenhou).payment, Payment::Ron(7700));
+        assert_eq!(ron(3, 60, NON_DEALER, &tenhou).payment, Payment::Ron(7700));
+        assert_eq!(ron(4, 30, DEALER, &tenhou).payment, Payment::Ron(11600));
+
+        assert_eq!(ron(4, 30, NON_DEALER, &wrc).payment, Payment::Ron(8000));
+        assert_eq!(ron(3, 60, NON_DEALER, &wrc).payment, Payment::Ron(8000));
+        assert_eq!(ron(4, 30, NON_DEALER, &wrc).limit, Some(Limit::Mangan));
+    }
+
+    #[test]
+    fn tsumo_split() {
+        let rules = Ruleset::tenhou();
+        let tsumo = |han, fu, player| payment(han, fu, WinMethod::Tsumo, player, TABLE, &rules);
+
+        assert_eq!(
+            tsumo(1, 30, NON_DEALER).payment,
+            Payment::TsumoSplit {
+                dealer: 500,
+                non_dealer: 300
+            }
+        );
+        assert_eq!(tsumo(1, 30, DEALER).payment, Payment::TsumoAll(500));
+        assert_eq!(
+            tsumo(5, 30, NON_DEALER).payment,
+            Payment::TsumoSplit {
+                dealer: 4000,
+                non_dealer: 2000
+            }
+        );
+        assert_eq!(tsumo(5, 30, DEALER).payment, Payment::TsumoAll(4000));
+
+        assert_eq!(tsumo(1, 30, NON_DEALER).payment.total(), 1100);
+        assert_eq!(tsumo(1, 30, DEALER).payment.total(), 1500);
+    }
+
+    #[test]
+    fn honba_and_deposits() {
+        let rules = Ruleset::tenhou();
+        let table = TableState {
+            honba: 2,
+            riichi_sticks: 3,
+        };
+
+        let payout = payment(1, 30, WinMethod::Ron, NON_DEALER, table, &rules);
+        assert_eq!(payout.payment, Payment::Ron(1600));
+        assert_eq!(payout.deposits, 3000);
+        assert_eq!(payout.total(), 4600);
+
+        let payout = payment(1, 30, WinMethod::Tsumo, NON_DEALER, table, &rules);
+        assert_eq!(
+            payout.payment,
+            Payment::TsumoSplit {
+                dealer: 700,
+                non_dealer: 500
+            }
+        );
+        assert_eq!(payout.total(), 1700 + 3000);
+
+        let payout = payment(1, 30, WinMethod::Tsumo, DEALER, table, &rules);
+        assert_eq!(payout.payment, Payment::TsumoAll(700));
+    }
+
+    #[test]
+    fn yakuman() {
+        let payout = yakuman_payment(1, WinMethod::Ron, NON_DEALER, TABLE);
+        assert_eq!(payout.limit, Some(Limit::Yakuman));
+        assert_eq!(payout.payment, Payment::Ron(32000));
+
+        let payout = yakuman_payment(2, WinMethod::Ron, DEALER, TABLE);
+        assert_eq!(payout.payment, Payment::Ron(96000));
+
+        let payout = yakuman_payment(1, WinMethod::Tsumo, NON_DEALER, TABLE);
+        assert_eq!(
+            payout.payment,
+            Payment::TsumoSplit {
+                dealer: 16000,
+                non_dealer: 8000
+            }
+        );
+    }
+
+    #[test]
+    fn multiple_ron() {
+        let table = TableState {
+            honba: 1,
+            riichi_sticks: 2,
+        };
+        let payouts = [
+            payment(1, 30, WinMethod::Ron, NON_DEALER, table, &Ruleset::tenhou()),
+            payment(5, 30, WinMethod::Ron, DEALER, table, &Ruleset::tenhou()),
+        ];
+
+        let winners = settle_ron(&payouts, &Ruleset::tenhou());
+        assert_eq!(winners.len(), 2);
+        assert_eq!(winners[0].total(), 1300 + 2000);
+        assert_eq!(winners[1].total(), 12300);
+
+        let winners = settle_ron(&payouts, &Ruleset::wrc());
+        assert_eq!(winners, [payouts[0]]);
+    }
+}
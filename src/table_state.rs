@@ -0,0 +1,10 @@
+//! Table counters that carry over between hands.
+
+/// Sticks sitting on the table when a hand is won.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TableState {
+    /// Repeat counters, each worth 300 points to the winner.
+    pub honba: u32,
+    /// Unclaimed 1,000 point riichi deposits, collected by the winner.
+    pub riichi_sticks: u32,
+}
@@ -0,0 +1,29 @@
+//! Fixtures shared by the unit tests.
+
+use crate::{
+    mentsu::Mentsu,
+    parser,
+    player_state::{PlayerState, Wind},
+};
+
+/// A non-dealer in the south seat during the east round.
+pub const NON_DEALER: PlayerState = PlayerState {
+    seat_wind: Wind::South,
+    round_wind: Wind::East,
+    dealer: false,
+};
+
+/// The dealer during the east round.
+pub const DEALER: PlayerState = PlayerState {
+    seat_wind: Wind::East,
+    round_wind: Wind::East,
+    dealer: true,
+};
+
+/// Parses `s` and returns every interpretation of it as a winning hand.
+pub fn interpret(s: &str) -> (parser::Hand, Vec<Vec<Mentsu>>) {
+    let hand = parser::parse(s).unwrap();
+    let i13s = parser::interpret(&hand.tiles, &hand.melds, hand.win_tile, hand.win_method);
+
+    (hand, i13s)
+}
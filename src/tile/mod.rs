@@ -1,20 +1,35 @@
+//! Individual tiles.
+
+use serde::Serialize;
 use std::hash::Hash;
 
 mod suit;
 
-pub use suit::{Suit, SuitIter};
+pub use suit::Suit;
 
 use crate::player_state::Wind;
 
-#[derive(Debug, Copy, Clone, Eq, Ord)]
+/// Number of distinct tiles: 9 each of man, pin and sou, and 7 honors.
+pub const KINDS: usize = 34;
+
+/// A single tile. Honors use values 1-4 for the winds (east to north)
+/// and 5-7 for the dragons (white, green, red).
+#[derive(Debug, Copy, Clone, Eq, Serialize)]
 pub struct Tile {
     pub value: u32,
     pub suit: Suit,
+    /// Red five. Ignored by comparisons, so a red five forms sets with
+    /// regular fives, but kept for display and dora counting.
+    pub aka: bool,
 }
 
 impl std::fmt::Display for Tile {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}", self.value, self.suit)
+        if self.aka {
+            write!(f, "0{}", self.suit)
+        } else {
+            write!(f, "{}{}", self.value, self.suit)
+        }
     }
 }
 
@@ -26,14 +41,15 @@ impl PartialEq for Tile {
 
 impl PartialOrd for Tile {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match self.value.partial_cmp(&other.value) {
-            Some(core::cmp::Ordering::Equal) => (),
-            ord => return ord,
-        }
-        match self.suit.partial_cmp(&other.suit) {
-            Some(core::cmp::Ordering::Equal) => return Some(core::cmp::Ordering::Equal),
-            ord => return ord,
-        }
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Tile {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.value
+            .cmp(&other.value)
+            .then_with(|| self.suit.cmp(&other.suit))
     }
 }
 
@@ -44,15 +60,52 @@ impl Hash for Tile {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub enum WinMethod {
-    Tsumo,
-    Ron,
-}
-
 impl Tile {
     pub const fn new(value: u32, suit: Suit) -> Self {
-        Self { value, suit }
+        Self {
+            value,
+            suit,
+            aka: false,
+        }
+    }
+
+    /// Creates the red five of `suit`.
+    pub const fn new_aka(suit: Suit) -> Self {
+        Self {
+            value: 5,
+            suit,
+            aka: true,
+        }
+    }
+
+    /// Iterates over one of each of the 34 distinct tiles, in order.
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..KINDS).map(Self::from_index)
+    }
+
+    /// Returns the tile's position among the 34 distinct tiles: man, pin
+    /// and sou 1-9, then the honors.
+    pub(crate) const fn index(self) -> usize {
+        let suit = match self.suit {
+            Suit::Man => 0,
+            Suit::Pin => 1,
+            Suit::Sou => 2,
+            Suit::Honor => 3,
+        };
+
+        suit * 9 + self.value as usize - 1
+    }
+
+    /// Inverse of [`Tile::index`].
+    pub(crate) const fn from_index(i: usize) -> Self {
+        let suit = match i / 9 {
+            0 => Suit::Man,
+            1 => Suit::Pin,
+            2 => Suit::Sou,
+            _ => Suit::Honor,
+        };
+
+        Self::new((i % 9) as u32 + 1, suit)
     }
 
     /// Returns `true` if both tiles can appear in the same sequence.
@@ -81,22 +134,66 @@ impl Tile {
         if value > 9 {
             None
         } else {
-            Some(Self {
-                value,
-                suit: self.suit,
-            })
+            Some(Self::new(value, self.suit))
+        }
+    }
+
+    /// Returns the dora indicated by this tile: the next tile in its suit,
+    /// wrapping 9 to 1, North to East and Red to White.
+    pub const fn dora_from_indicator(self) -> Self {
+        let value = match (self.suit, self.value) {
+            (Suit::Honor, 4) => 1,
+            (Suit::Honor, 7) => 5,
+            (Suit::Honor, v) => v + 1,
+            (_, 9) => 1,
+            (_, v) => v + 1,
+        };
+
+        Self::new(value, self.suit)
+    }
+
+    /// Returns `true` for the tiles allowed in ryuuiisou: 2, 3, 4, 6 and 8
+    /// of sou, and the green dragon.
+    pub fn green(self) -> bool {
+        match self.suit {
+            Suit::Sou => matches!(self.value, 2 | 3 | 4 | 6 | 8),
+            Suit::Honor => self.value == 6,
+            _ => false,
         }
     }
 
+    pub fn dragon(self) -> bool {
+        self.honor() && matches!(self.value, 5..=7)
+    }
+
     pub fn wind(self) -> Option<Wind> {
-        if !self.honor() { return None }
+        if !self.honor() {
+            return None;
+        }
 
         match self.value {
             1 => Some(Wind::East),
             2 => Some(Wind::South),
             3 => Some(Wind::West),
             4 => Some(Wind::North),
-            _ => None
+            _ => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dora_wraps_around() {
+        let dora = |value, suit| Tile::new(value, suit).dora_from_indicator();
+
+        assert_eq!(dora(3, Suit::Man), Tile::new(4, Suit::Man));
+        assert_eq!(dora(9, Suit::Pin), Tile::new(1, Suit::Pin));
+        assert_eq!(dora(1, Suit::Honor), Tile::new(2, Suit::Honor));
+        assert_eq!(dora(4, Suit::Honor), Tile::new(1, Suit::Honor));
+        assert_eq!(dora(5, Suit::Honor), Tile::new(6, Suit::Honor));
+        assert_eq!(dora(7, Suit::Honor), Tile::new(5, Suit::Honor));
+    }
+}
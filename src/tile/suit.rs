@@ -1,6 +1,8 @@
+use serde::Serialize;
 use strum_macros::EnumIter;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter)]
+/// A tile suit, written `m`, `p`, `s` and `z` in hand strings.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter, Serialize)]
 pub enum Suit {
     Man,
     Pin,
@@ -23,14 +25,26 @@ impl std::fmt::Display for Suit {
     }
 }
 
-impl From<char> for Suit {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Suit {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            'm' => Self::Man,
-            's' => Self::Sou,
-            'p' => Self::Pin,
-            'z' => Self::Honor,
-            _ => unreachable!(),
+            'm' => Ok(Self::Man),
+            's' => Ok(Self::Sou),
+            'p' => Ok(Self::Pin),
+            'z' => Ok(Self::Honor),
+            c => Err(c),
+        }
+    }
+}
+
+impl Suit {
+    /// Returns the highest tile value in this suit.
+    pub const fn max_value(self) -> u32 {
+        match self {
+            Self::Honor => 7,
+            _ => 9,
         }
     }
 }
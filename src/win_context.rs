@@ -0,0 +1,167 @@
+//! Circumstances of a win that the tiles alone don't show.
+
+use crate::parser::WinMethod;
+
+/// Situational facts about a win that can't be read from the tiles.
+///
+/// Yaku only count flags that can hold together, so an impossible context
+/// never adds han. [`WinContext::validate`] reports what is wrong with it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct WinContext {
+    pub riichi: bool,
+    /// Riichi declared on the first discard. Replaces `riichi`.
+    pub double_riichi: bool,
+    /// Won within one go-around of declaring riichi, without any calls.
+    pub ippatsu: bool,
+    /// Won on the replacement tile drawn after a kan.
+    pub rinshan: bool,
+    /// Won on the last tile of the wall (haitei) or its discard (houtei).
+    pub haitei: bool,
+    /// Won by robbing another player's added kan.
+    pub chankan: bool,
+    /// Won on the player's first draw, before any calls.
+    pub first_turn: bool,
+}
+
+/// An impossible combination of [`WinContext`] flags.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ContextError {
+    RiichiAndDoubleRiichi,
+    IppatsuWithoutRiichi,
+    ChankanByTsumo,
+    RinshanByRon,
+    /// The dead wall replacement tile is never the last tile of the wall.
+    RinshanHaitei,
+    /// A first turn win happens before the player could declare riichi.
+    FirstTurnRiichi,
+}
+
+impl std::fmt::Display for ContextError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::RiichiAndDoubleRiichi => "riichi and double riichi are exclusive",
+                Self::IppatsuWithoutRiichi => "ippatsu requires riichi",
+                Self::ChankanByTsumo => "chankan is always a ron",
+                Self::RinshanByRon => "rinshan kaihou is always a tsumo",
+                Self::RinshanHaitei => "rinshan kaihou can't also be haitei",
+                Self::FirstTurnRiichi => "a first turn win can't follow riichi",
+            }
+        )
+    }
+}
+
+impl std::error::Error for ContextError {}
+
+impl WinContext {
+    /// Returns `true` if either kind of riichi was declared.
+    pub const fn any_riichi(self) -> bool {
+        self.riichi || self.double_riichi
+    }
+
+    /// Checks that the flags can all hold for a win by `win_method`.
+    pub const fn validate(self, win_method: WinMethod) -> Result<(), ContextError> {
+        let tsumo = matches!(win_method, WinMethod::Tsumo);
+
+        if self.riichi && self.double_riichi {
+            Err(ContextError::RiichiAndDoubleRiichi)
+        } else if self.ippatsu && !self.any_riichi() {
+            Err(ContextError::IppatsuWithoutRiichi)
+        } else if self.chankan && tsumo {
+            Err(ContextError::ChankanByTsumo)
+        } else if self.rinshan && !tsumo {
+            Err(ContextError::RinshanByRon)
+        } else if self.rinshan && self.haitei {
+            Err(ContextError::RinshanHaitei)
+        } else if self.first_turn && self.any_riichi() {
+            Err(ContextError::FirstTurnRiichi)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate() {
+        let ron = WinMethod::Ron;
+        let tsumo = WinMethod::Tsumo;
+
+        let riichi = WinContext {
+            riichi: true,
+            ..Default::default()
+        };
+        assert_eq!(riichi.validate(ron), Ok(()));
+        assert_eq!(WinContext::default().validate(tsumo), Ok(()));
+
+        let check = |ctx: WinContext, win_method| ctx.validate(win_method).unwrap_err();
+
+        assert_eq!(
+            check(
+                WinContext {
+                    double_riichi: true,
+                    ..riichi
+                },
+                ron
+            ),
+            ContextError::RiichiAndDoubleRiichi
+        );
+        assert_eq!(
+            check(
+                WinContext {
+                    ippatsu: true,
+                    ..Default::default()
+                },
+                ron
+            ),
+            ContextError::IppatsuWithoutRiichi
+        );
+        assert_eq!(
+            check(
+                WinContext {
+                    chankan: true,
+                    ..Default::default()
+                },
+                tsumo
+            ),
+            ContextError::ChankanByTsumo
+        );
+        assert_eq!(
+            check(
+                WinContext {
+                    rinshan: true,
+                    ..Default::default()
+                },
+                ron
+            ),
+            ContextError::RinshanByRon
+        );
+        assert_eq!(
+            check(
+                WinContext {
+                    rinshan: true,
+                    haitei: true,
+                    ..Default::default()
+                },
+                tsumo
+            ),
+            ContextError::RinshanHaitei
+        );
+        assert_eq!(
+            check(
+                WinContext {
+                    first_turn: true,
+                    ..riichi
+                },
+                tsumo
+            ),
+            ContextError::FirstTurnRiichi
+        );
+    }
+}
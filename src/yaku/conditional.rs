@@ -1,46 +1,54 @@
 use super::{OpenScore, Yaku};
+use crate::mentsu;
 
+/// Yaku that depend on how the hand was won rather than its tiles.
 pub static CONDITIONAL_YAKU: &[Yaku] = &[
     Yaku {
         name: "Riichi",
         desc: "A closed hand declaring tenpai. Costs 1,000 pts.",
         han: 1,
         open_score: OpenScore::Illegal,
-        f: |_, _| true,
+        superseded_by: &["Double riichi"],
+        f: |_, _, ctx| ctx.riichi,
     },
     Yaku {
         name: "Double riichi",
         desc: "Declared riichi on first turn before a tile call.",
         han: 2,
         open_score: OpenScore::Illegal,
-        f: |_, _| true,
+        superseded_by: &[],
+        f: |_, _, ctx| ctx.double_riichi,
     },
     Yaku {
         name: "Ippatsu",
         desc: "Won with riichi before your next discard. Invalidated by calls.",
         han: 1,
         open_score: OpenScore::Illegal,
-        f: |_, _| true,
+        superseded_by: &[],
+        f: |_, _, ctx| ctx.ippatsu && ctx.any_riichi(),
     },
     Yaku {
         name: "Rinshan kaihou",
         desc: "Won by drawing a tile from the dead wall.",
         han: 1,
-        open_score: OpenScore::Illegal,
-        f: |_, _| true,
+        open_score: OpenScore::Full,
+        superseded_by: &[],
+        f: |vec_mn, _, ctx| ctx.rinshan && mentsu::tsumo(vec_mn) && vec_mn.iter().any(|m| m.quad()),
     },
     Yaku {
         name: "Haitei/houtei",
         desc: "Won by drawing or calling the final tile from the wall.",
         han: 1,
-        open_score: OpenScore::Illegal,
-        f: |_, _| true,
+        open_score: OpenScore::Full,
+        superseded_by: &[],
+        f: |_, _, ctx| ctx.haitei && !ctx.rinshan,
     },
     Yaku {
         name: "Chankan",
         desc: "Won by calling ron on an added kan.",
         han: 1,
         open_score: OpenScore::Full,
-        f: |_, _| true,
+        superseded_by: &[],
+        f: |vec_mn, _, ctx| ctx.chankan && !mentsu::tsumo(vec_mn),
     },
 ];
@@ -1,24 +1,71 @@
-use crate::mentsu::Mentsu;
+//! Every yaku, and the rules for which ones combine.
+
+use crate::mentsu::{self, Mentsu};
 use crate::player_state::PlayerState;
+use crate::ruleset::Ruleset;
+use crate::win_context::WinContext;
 
 mod conditional;
 mod regular;
-mod weird;
 mod yakuman;
 
 // Export statics under this scope
 pub use conditional::CONDITIONAL_YAKU;
 pub use regular::REGULAR_YAKU;
-pub use weird::WEIRD_YAKU;
 pub use yakuman::YAKUMAN;
 
+/// Returns the name of every yaku, in table order.
+pub fn names() -> impl Iterator<Item = &'static str> {
+    REGULAR_YAKU
+        .iter()
+        .chain(CONDITIONAL_YAKU)
+        .chain(YAKUMAN)
+        .map(|y| y.name)
+}
+
+/// Returns every yaku the hand scores under `rules`, after [`resolve`].
+pub fn find(
+    hand: &[Mentsu],
+    player: PlayerState,
+    ctx: WinContext,
+    rules: &Ruleset,
+) -> Vec<&'static Yaku> {
+    let found = REGULAR_YAKU
+        .iter()
+        .chain(CONDITIONAL_YAKU)
+        .chain(YAKUMAN)
+        .filter(|y| y.valid_for(hand, player, ctx, rules))
+        .collect();
+
+    resolve(found)
+}
+
+/// Removes yaku that don't stack with others in the same hand: any yaku
+/// superseded by another present yaku, and every regular yaku if there is a
+/// yakuman.
+pub fn resolve(mut found: Vec<&'static Yaku>) -> Vec<&'static Yaku> {
+    if found.iter().any(|y| y.yakuman()) {
+        found.retain(|y| y.yakuman());
+    }
+
+    let names: Vec<&str> = found.iter().map(|y| y.name).collect();
+
+    found.retain(|y| !y.superseded_by.iter().any(|s| names.contains(s)));
+
+    found
+}
+
+/// A yaku: a pattern that makes a hand worth han.
 #[derive(Copy, Clone, Debug)]
 pub struct Yaku {
     pub name: &'static str,
     pub desc: &'static str,
+    /// Han, or for yakuman, how many yakuman the yaku is worth.
     han: u32,
     pub open_score: OpenScore,
-    pub f: fn(&[Mentsu], PlayerState) -> bool,
+    /// Names of yaku that replace this one when both are present.
+    pub superseded_by: &'static [&'static str],
+    pub f: fn(&[Mentsu], PlayerState, WinContext) -> bool,
 }
 
 impl Yaku {
@@ -26,13 +73,55 @@ impl Yaku {
         self.name
     }
 
-    pub fn valid_for(&self, mentsu: &[Mentsu], player: PlayerState) -> bool {
-        (self.f)(mentsu, player)
+    pub fn yakuman(&self) -> bool {
+        YAKUMAN.iter().any(|y| y.name == self.name)
+    }
+
+    /// Returns how many yakuman the yaku is worth: 0 for regular yaku, and
+    /// at most 1 unless `rules` allow double yakuman.
+    pub fn yakuman_count(&self, rules: &Ruleset) -> u32 {
+        match (self.yakuman(), rules.double_yakuman) {
+            (false, _) => 0,
+            (true, true) => self.han,
+            (true, false) => 1,
+        }
+    }
+
+    /// Returns `true` if the hand scores this yaku under `rules`.
+    pub fn valid_for(
+        &self,
+        mentsu: &[Mentsu],
+        player: PlayerState,
+        ctx: WinContext,
+        rules: &Ruleset,
+    ) -> bool {
+        if !rules.yaku_enabled(self.name) {
+            return false;
+        }
+
+        if matches!(self.open_score(rules), OpenScore::Illegal) && !mentsu::menzenchin(mentsu) {
+            return false;
+        }
+
+        (self.f)(mentsu, player, ctx)
+    }
+
+    /// Returns how the yaku scores in an open hand, after applying `rules`.
+    pub fn open_score(&self, rules: &Ruleset) -> OpenScore {
+        match self.name {
+            "Tanyao" if !rules.kuitan => OpenScore::Illegal,
+            _ => self.open_score,
+        }
     }
 
     /// Returns the yaku's han value. If applicable, accounts for reduced han
-    /// when scored open.
-    pub const fn han(self, menzenchin: bool) -> u32 {
+    /// when scored open. Yakuman are worth no han; see
+    /// [`Yaku::yakuman_count`].
+    pub fn han(self, menzenchin: bool) -> u32 {
+        if self.yakuman() {
+            return 0;
+        }
+
         match (self.open_score, menzenchin) {
             (OpenScore::Full, _) => self.han,
             (OpenScore::Reduced | OpenScore::Illegal, true) => self.han,
@@ -42,9 +131,219 @@ impl Yaku {
     }
 }
 
+/// How a yaku scores once the hand is open.
 #[derive(Copy, Clone, Debug)]
 pub enum OpenScore {
     Full,
     Reduced,
     Illegal,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{self, DEALER, NON_DEALER};
+
+    /// Names of the yaku [`find`] returns for each interpretation of `s`.
+    fn yaku_for(s: &str, player: PlayerState, ctx: WinContext) -> Vec<Vec<&'static str>> {
+        let (_, i13s) = test_util::interpret(s);
+
+        i13s.iter()
+            .map(|m| {
+                find(m, player, ctx, &Ruleset::default())
+                    .iter()
+                    .map(|y| y.name)
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn yaku_with(s: &str, ctx: WinContext) -> Vec<Vec<&'static str>> {
+        yaku_for(s, NON_DEALER, ctx)
+    }
+
+    fn yaku_of(s: &str) -> Vec<Vec<&'static str>> {
+        yaku_with(s, WinContext::default())
+    }
+
+    /// Returns `true` if any interpretation of `s` scores `name`.
+    fn has(s: &str, name: &str) -> bool {
+        yaku_of(s).iter().any(|y| y.contains(&name))
+    }
+
+    /// The han `name` is worth in `s`, which is open if it has a call.
+    fn han_of(s: &str, name: &str) -> u32 {
+        let (hand, _) = test_util::interpret(s);
+        let yaku = REGULAR_YAKU.iter().find(|y| y.name == name).unwrap();
+
+        yaku.han(hand.melds.iter().all(|m| !m.open))
+    }
+
+    #[test]
+    fn pinfu_pair() {
+        // West isn't the seat or round wind, so it's worth no fu.
+        assert!(yaku_of("234m567p678s33z23s 4s")[0].contains(&"Pinfu"));
+        assert!(!yaku_of("234m567p678s22z23s 4s")[0].contains(&"Pinfu"));
+        assert!(!yaku_of("234m567p678s55z23s 4s")[0].contains(&"Pinfu"));
+    }
+
+    #[test]
+    fn impossible_context_adds_nothing() {
+        let ron = "234m567p678s33z23s 4s";
+        let tsumo = "234m567p678s33z23s4s";
+        let kan = "(2222m)567p678s33z23s4s";
+        let has = |s, ctx, name| yaku_with(s, ctx)[0].contains(&name);
+
+        let ippatsu = WinContext {
+            ippatsu: true,
+            ..Default::default()
+        };
+        assert!(!has(ron, ippatsu, "Ippatsu"));
+        assert!(has(
+            ron,
+            WinContext {
+                riichi: true,
+                ..ippatsu
+            },
+            "Ippatsu"
+        ));
+
+        let both = WinContext {
+            riichi: true,
+            double_riichi: true,
+            ..Default::default()
+        };
+        assert!(!has(ron, both, "Riichi"));
+        assert!(has(ron, both, "Double riichi"));
+
+        let chankan = WinContext {
+            chankan: true,
+            ..Default::default()
+        };
+        assert!(has(ron, chankan, "Chankan"));
+        assert!(!has(tsumo, chankan, "Chankan"));
+
+        let rinshan = WinContext {
+            rinshan: true,
+            haitei: true,
+            ..Default::default()
+        };
+        assert!(has(kan, rinshan, "Rinshan kaihou"));
+        assert!(!has(kan, rinshan, "Haitei/houtei"));
+        assert!(!has(ron, rinshan, "Rinshan kaihou"));
+    }
+
+    #[test]
+    fn terminals_in_every_set() {
+        assert!(has("123m789m123p99s78s 9s", "Junchan"));
+        assert!(!has("123m789m123p99s78s 9s", "Chanta"));
+        assert!(has("123m789m123p11z78s 9s", "Chanta"));
+        assert!(!has("123m789m123p11z78s 9s", "Junchan"));
+        assert!(!has("123m789m234p99s78s 9s", "Junchan"));
+
+        assert!(has("111m999p111s11z99m 9m", "Honroutou"));
+        assert!(!has("111m999p123s11z99m 9m", "Honroutou"));
+    }
+
+    #[test]
+    fn flushes() {
+        assert!(has("123m456m789m11z22z 2z", "Honitsu"));
+        assert!(!has("123m456m789m11z22z 2z", "Chinitsu"));
+        assert!(!has("123m456m789p11z22z 2z", "Honitsu"));
+        assert!(has("123m456m789m11m22m 2m", "Chinitsu"));
+        assert!(!has("123m456m789m11m22m 2m", "Honitsu"));
+
+        assert_eq!(han_of("123m456m789m11z22z 2z", "Honitsu"), 3);
+        assert_eq!(han_of("[123m]456m789m11z22z 2z", "Honitsu"), 2);
+        assert_eq!(han_of("123m456m789m11m22m 2m", "Chinitsu"), 6);
+        assert_eq!(han_of("[123m]456m789m11m22m 2m", "Chinitsu"), 5);
+    }
+
+    #[test]
+    fn dragons_and_winds() {
+        assert!(has("555z666z77z123m45p 6p", "Shousangen"));
+        assert!(!has("555z666z77z123m45p 6p", "Daisangen"));
+        assert!(!has("555z11z123m456p78s 9s", "Shousangen"));
+        assert!(has("555z666z777z123m4p 4p", "Daisangen"));
+
+        assert!(has("111z222z333z44z12m 3m", "Shousuushii"));
+        assert!(!has("111z222z333z44z12m 3m", "Daisuushii"));
+        assert!(!has("111z222z55z123m45p 6p", "Shousuushii"));
+        assert!(has("111z222z333z444z5z 5z", "Daisuushii"));
+    }
+
+    #[test]
+    fn kans() {
+        assert!(has("(1111m)(2222p)(3333s)456m7z 7z", "Sankantsu"));
+        assert!(!has("(1111m)(2222p)333s456m7z 7z", "Sankantsu"));
+        assert!(has("(1111m)(2222p)(3333s)(4444z)5z 5z", "Suukantsu"));
+        assert!(!has("(1111m)(2222p)(3333s)(4444z)5z 5z", "Sankantsu"));
+    }
+
+    #[test]
+    fn single_suit_yakuman() {
+        assert!(has("234s666s888s66z23s 4s", "Ryuuiisou"));
+        assert!(!has("234s666s888s55z23s 4s", "Ryuuiisou"));
+        assert!(has("1112345678899m 9m", "Chuuren poutou"));
+        assert!(has("1112345678999m 5m", "Junsei chuuren poutou"));
+        assert!(!has("1112345678999m 5m", "Chuuren poutou"));
+        assert!(!has("1112345678899m 9m", "Junsei chuuren poutou"));
+        assert!(!has("123345678999m2m 2m", "Chuuren poutou"));
+    }
+
+    #[test]
+    fn first_draw() {
+        let first_turn = WinContext {
+            first_turn: true,
+            ..Default::default()
+        };
+        let tsumo = "123m456m789p11s23s4s";
+
+        assert!(yaku_for(tsumo, DEALER, first_turn)[0].contains(&"Tenhou"));
+        assert!(yaku_for(tsumo, NON_DEALER, first_turn)[0].contains(&"Chiihou"));
+        assert!(!yaku_for(tsumo, NON_DEALER, first_turn)[0].contains(&"Tenhou"));
+        assert!(!yaku_with("123m456m789p11s23s 4s", first_turn)[0].contains(&"Chiihou"));
+        assert!(!yaku_of(tsumo)[0].contains(&"Chiihou"));
+    }
+
+    #[test]
+    fn sanshoku_needs_suits() {
+        assert!(has("111m111p111s234s5s 5s", "Sanshoku doukou"));
+        assert!(!has("111m111p111z234s5s 5s", "Sanshoku doukou"));
+    }
+
+    #[test]
+    fn peikou_ignores_which_twin_won() {
+        // Ron marks one of the twin sequences open, with the wait.
+        assert_eq!(yaku_of("1122m3m456p789s55z 3m"), [["Iipeikou"]]);
+        assert!(
+            yaku_of("112233m11223p55z 3p")
+                .iter()
+                .any(|y| y.contains(&"Ryanpeikou") && !y.contains(&"Iipeikou"))
+        );
+
+        // Three copies of one sequence make only one twin.
+        let triple = yaku_of("123s123s123s111z2m2m");
+        assert!(triple.iter().any(|y| y.contains(&"Iipeikou")));
+        assert!(!triple.iter().any(|y| y.contains(&"Ryanpeikou")));
+        assert!(!has("1112223334445m 5m", "Ryanpeikou"));
+    }
+
+    #[test]
+    fn yakuman_replaces_regular_yaku() {
+        // Toitoi, Sanankou, Honroutou and Menzen tsumo all drop out.
+        assert_eq!(yaku_of("111m999p111s99s11z1z"), [["Suuankou"]]);
+    }
+
+    #[test]
+    fn double_yakuman_waits() {
+        assert!(has("111m999p111s111z9s 9s", "Suuankou tanki"));
+        assert!(!has("111m999p111s111z9s 9s", "Suuankou"));
+        assert!(has("111m999p111s11z99s9s", "Suuankou"));
+        assert!(!has("111m999p111s11z99s9s", "Suuankou tanki"));
+
+        assert!(has("19m19p19s1234567z 1m", "Kokushi musou juusanmen"));
+        assert!(has("119m19p19s123456z 7z", "Kokushi musou"));
+        assert!(!has("119m19p19s123456z 7z", "Kokushi musou juusanmen"));
+    }
+}
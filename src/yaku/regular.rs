@@ -1,28 +1,36 @@
 use super::{OpenScore, Yaku};
-use crate::{mentsu::kind::Kind, parser::WinWait, tile::Suit};
+use crate::{
+    mentsu::{self, kind::Kind},
+    parser::WinWait,
+    tile::Suit,
+};
 use strum::IntoEnumIterator;
 
+/// Yaku read from the shape of the hand.
 pub static REGULAR_YAKU: &[Yaku] = &[
     Yaku {
         name: "Tanyao",
-        desc: "All simples",
+        desc: "All simples. Only scored open with kuitan.",
         han: 1,
-        open_score: OpenScore::Illegal,
-        f: |vec_mn, _state| vec_mn.iter().all(|m| !m.contains_terminal()),
+        open_score: OpenScore::Full,
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| vec_mn.iter().all(|m| !m.contains_terminal() && !m.honor()),
     },
     Yaku {
         name: "Menzen-tsumo",
         desc: "Won by tsumo in menzenchin",
         han: 1,
         open_score: OpenScore::Illegal,
-        f: |vec_mn, _state| !vec_mn.iter().any(|m| m.open),
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| !vec_mn.iter().any(|m| m.open),
     },
     Yaku {
         name: "Yakuhai (Round wind)",
         desc: "A triplet/quad of round wind tiles",
         han: 1,
         open_score: OpenScore::Full,
-        f: |vec_mn, state| {
+        superseded_by: &[],
+        f: |vec_mn, state, _ctx| {
             vec_mn
                 .iter()
                 .any(|m| m.triplet() && m.wind().is_some_and(|w| w == state.round_wind))
@@ -33,7 +41,8 @@ pub static REGULAR_YAKU: &[Yaku] = &[
         desc: "A triplet/quad of seat wind tiles",
         han: 1,
         open_score: OpenScore::Full,
-        f: |vec_mn, state| {
+        superseded_by: &[],
+        f: |vec_mn, state, _ctx| {
             vec_mn
                 .iter()
                 .any(|m| m.triplet() && m.wind().is_some_and(|w| w == state.seat_wind))
@@ -41,38 +50,48 @@ pub static REGULAR_YAKU: &[Yaku] = &[
     },
     Yaku {
         name: "Chanta",
-        desc: "All mentsu contain at least one terminal or honor",
+        desc: "All mentsu contain a terminal or honor, with at least one honor and one sequence",
         han: 2,
         open_score: OpenScore::Reduced,
-        f: |vec_mn, _state| vec_mn.iter().all(|m| m.contains_terminal() || m.honor()),
+        superseded_by: &["Junchan"],
+        f: |vec_mn, _state, _ctx| {
+            vec_mn.iter().all(|m| m.contains_terminal() || m.honor())
+                && vec_mn.iter().any(|m| m.honor())
+                && vec_mn.iter().any(|m| m.sequence())
+        },
     },
     Yaku {
-        name: "Junchan (incompatible w chanta)",
-        desc: "All mentsu contain at least one terminal",
+        name: "Junchan",
+        desc: "All mentsu contain a terminal, with at least one sequence",
         han: 3,
         open_score: OpenScore::Reduced,
-        f: |vec_mn, _state| vec_mn.iter().all(|m| m.contains_terminal()),
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| {
+            vec_mn.iter().all(|m| m.contains_terminal()) && vec_mn.iter().any(|m| m.sequence())
+        },
     },
     Yaku {
         name: "Pinfu",
         desc: "Minimum fu; no triplets, non-yakuhai pair, and ryanmen wait",
         han: 1,
         open_score: OpenScore::Illegal,
-        f: |vec_mn, _state| {
+        superseded_by: &[],
+        f: |vec_mn, state, _ctx| {
             vec_mn.iter().all(|m| !m.triplet()) // No triplets
-                && !vec_mn.iter().find(|m| m.pair()).unwrap().honor() // Non-honor*** pair FIX LATER
-                && !vec_mn.iter().any(|m| m.open && m.win_wait.is_none()) // Menzenchin
+                && !vec_mn.iter().any(|m| m.pair() && m.yakuhai(state)) // Non-yakuhai pair
+                && mentsu::menzenchin(vec_mn)
                 && vec_mn.iter().any(|m| m.win_wait.is_some_and(|w| w == WinWait::Ryanmen)) // Ryanmen
         },
     },
     Yaku {
         name: "Yakuhai (White Dragon)",
-        desc: "White dragon triplet",
+        desc: "White dragon triplet/quad",
         han: 1,
         open_score: OpenScore::Full,
-        f: |vec_mn, _state| {
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| {
             vec_mn.iter().any(|m| {
-                if let Kind::Triplet(t) = m.kind {
+                if let Kind::Triplet(t) | Kind::Quad(t) = m.kind {
                     return t.honor() && t.value == 5;
                 }
 
@@ -82,12 +101,13 @@ pub static REGULAR_YAKU: &[Yaku] = &[
     },
     Yaku {
         name: "Yakuhai (Green Dragon)",
-        desc: "Green dragon triplet",
+        desc: "Green dragon triplet/quad",
         han: 1,
         open_score: OpenScore::Full,
-        f: |vec_mn, _state| {
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| {
             vec_mn.iter().any(|m| {
-                if let Kind::Triplet(t) = m.kind {
+                if let Kind::Triplet(t) | Kind::Quad(t) = m.kind {
                     return t.honor() && t.value == 6;
                 }
 
@@ -97,12 +117,13 @@ pub static REGULAR_YAKU: &[Yaku] = &[
     },
     Yaku {
         name: "Yakuhai (Red Dragon)",
-        desc: "Red dragon triplet",
-        han: 2,
+        desc: "Red dragon triplet/quad",
+        han: 1,
         open_score: OpenScore::Full,
-        f: |vec_mn, _state| {
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| {
             vec_mn.iter().any(|m| {
-                if let Kind::Triplet(t) = m.kind {
+                if let Kind::Triplet(t) | Kind::Quad(t) = m.kind {
                     return t.honor() && t.value == 7;
                 }
 
@@ -115,35 +136,35 @@ pub static REGULAR_YAKU: &[Yaku] = &[
         desc: "Three concealed triplets",
         han: 2,
         open_score: OpenScore::Full,
-        f: |vec_mn, _state| vec_mn.iter().filter(|m| m.closed() && m.triplet()).count() >= 3,
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| vec_mn.iter().filter(|m| m.closed() && m.triplet()).count() >= 3,
     },
     Yaku {
         name: "Ryanpeikou",
         desc: "Twin identical sequences",
         han: 3,
         open_score: OpenScore::Illegal,
-        f: |vec_mn, _state| {
-            let mut twin_idx: Option<[usize; 2]> = None;
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| {
+            // Each sequence can only belong to one twin, so three copies of
+            // the same sequence are still just Iipeikou.
+            let mut used = vec![false; vec_mn.len()];
+            let mut twins = 0;
+
             for (i, m) in vec_mn.iter().enumerate().filter(|(_, m)| m.sequence()) {
-                // Don't double-match!
-                if twin_idx.is_some_and(|a| a.contains(&i)) {
+                if used[i] {
                     continue;
                 }
 
-                if let Some((j, _)) = vec_mn
-                    .iter()
-                    .enumerate()
-                    .find(|(j, other)| *j != i && *other == m)
+                if let Some(j) =
+                    (i + 1..vec_mn.len()).find(|&j| !used[j] && vec_mn[j].kind == m.kind)
                 {
-                    if twin_idx.is_some() {
-                        return true;
-                    }
-
-                    twin_idx = Some([i, j]);
+                    used[j] = true;
+                    twins += 1;
                 }
             }
 
-            false
+            twins == 2
         },
     },
     Yaku {
@@ -151,12 +172,13 @@ pub static REGULAR_YAKU: &[Yaku] = &[
         desc: "Identical sequences",
         han: 1,
         open_score: OpenScore::Illegal,
-        f: |vec_mn, _state| {
+        superseded_by: &["Ryanpeikou"],
+        f: |vec_mn, _state, _ctx| {
             for (i, m) in vec_mn.iter().enumerate().filter(|(_, m)| m.sequence()) {
                 if vec_mn
                     .iter()
                     .enumerate()
-                    .any(|(j, other)| j != i && other == m)
+                    .any(|(j, other)| j != i && other.kind == m.kind)
                 {
                     return true;
                 }
@@ -170,7 +192,8 @@ pub static REGULAR_YAKU: &[Yaku] = &[
         desc: "Three colors, same triplet",
         han: 2,
         open_score: OpenScore::Full,
-        f: |vec_mn, _state| {
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| {
             for (i, m) in vec_mn.iter().enumerate().filter(|(_, m)| m.triplet()) {
                 if vec_mn
                     .iter()
@@ -191,20 +214,21 @@ pub static REGULAR_YAKU: &[Yaku] = &[
         desc: "Three colors, same sequence",
         han: 2,
         open_score: OpenScore::Reduced,
-        f: |vec_mn, _state| {
-            for (i, m) in vec_mn.iter().enumerate().filter(|(_, m)| m.sequence()) {
-                if vec_mn
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| {
+            // Iipeikou can repeat a sequence, so look for both other suits
+            // rather than two other sequences.
+            vec_mn.iter().filter(|m| m.sequence()).any(|m| {
+                let mut suits: Vec<Suit> = vec_mn
                     .iter()
-                    .enumerate()
-                    .filter(|(j, n)| *j != i && m.eq_diff_suits(**n))
-                    .count()
-                    >= 2
-                {
-                    return true;
-                }
-            }
+                    .filter(|n| m.eq_diff_suits(**n))
+                    .map(|n| n.suit())
+                    .collect();
 
-            false
+                suits.sort();
+                suits.dedup();
+                suits.len() == 2
+            })
         },
     },
     Yaku {
@@ -212,14 +236,16 @@ pub static REGULAR_YAKU: &[Yaku] = &[
         desc: "All triplets",
         han: 2,
         open_score: OpenScore::Full,
-        f: |vec_mn, _state| vec_mn.iter().all(|m| !m.sequence()),
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| vec_mn.iter().filter(|m| m.triplet()).count() == 4,
     },
     Yaku {
         name: "Ittsuu",
         desc: "Pure straight",
         han: 2,
         open_score: OpenScore::Reduced,
-        f: |vec_mn, _state| {
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| {
             #[allow(unused)] // Isn't it used??
             let mut seqs = 0;
 
@@ -242,4 +268,63 @@ pub static REGULAR_YAKU: &[Yaku] = &[
             false
         },
     },
+    Yaku {
+        name: "Honitsu",
+        desc: "Half flush; one suit plus honors",
+        han: 3,
+        open_score: OpenScore::Reduced,
+        superseded_by: &["Chinitsu"],
+        f: |vec_mn, _state, _ctx| {
+            let mut suits = vec_mn.iter().filter(|m| !m.honor()).map(|m| m.suit());
+
+            suits.next().is_some_and(|s| suits.all(|o| o == s)) && vec_mn.iter().any(|m| m.honor())
+        },
+    },
+    Yaku {
+        name: "Chinitsu",
+        desc: "Full flush; one suit, no honors",
+        han: 6,
+        open_score: OpenScore::Reduced,
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| {
+            vec_mn
+                .iter()
+                .all(|m| !m.honor() && m.suit() == vec_mn[0].suit())
+        },
+    },
+    Yaku {
+        name: "Shousangen",
+        desc: "Little three dragons; two dragon triplets/quads and a dragon pair",
+        han: 2,
+        open_score: OpenScore::Full,
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| {
+            vec_mn.iter().filter(|m| m.triplet() && m.dragon()).count() == 2
+                && vec_mn.iter().any(|m| m.pair() && m.dragon())
+        },
+    },
+    Yaku {
+        name: "Honroutou",
+        desc: "All terminals and honors",
+        han: 2,
+        open_score: OpenScore::Full,
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| vec_mn.iter().all(|m| m.entirely_terminal() || m.honor()),
+    },
+    Yaku {
+        name: "Chiitoi",
+        desc: "Seven (7) pairs",
+        han: 2,
+        open_score: OpenScore::Illegal,
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| vec_mn.len() == 7 && vec_mn.iter().all(|m| m.pair()),
+    },
+    Yaku {
+        name: "Sankantsu",
+        desc: "Three quads",
+        han: 2,
+        open_score: OpenScore::Full,
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| vec_mn.iter().filter(|m| m.quad()).count() >= 3,
+    },
 ];
@@ -1,25 +1,190 @@
 use super::{OpenScore, Yaku};
+use crate::{
+    mentsu::{self, Mentsu},
+    parser::WinWait,
+    win_context::WinContext,
+};
 
+/// Limit hands. Any yakuman replaces every regular yaku, and several
+/// yakuman stack. `han` here counts yakuman: 2 for double yakuman.
 pub static YAKUMAN: &[Yaku] = &[
     Yaku {
         name: "Tsuuiisou",
         desc: "All honors",
-        han: 99,
+        han: 1,
         open_score: OpenScore::Full,
-        f: |vec_mn, _state| vec_mn.iter().all(|m| m.honor()),
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| vec_mn.iter().all(|m| m.honor()),
     },
     Yaku {
         name: "Chinroutou",
         desc: "All terminals",
-        han: 99,
+        han: 1,
         open_score: OpenScore::Full,
-        f: |vec_mn, _state| vec_mn.iter().all(|m| m.entirely_terminal()),
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| vec_mn.iter().all(|m| m.entirely_terminal()),
     },
     Yaku {
         name: "Suuankou",
         desc: "Four concealed triplets",
-        han: 99,
+        han: 1,
         open_score: OpenScore::Illegal,
-        f: |vec_mn, _state| vec_mn.iter().filter(|m| m.closed() && m.triplet()).count() >= 4,
+        superseded_by: &["Suuankou tanki"],
+        f: |vec_mn, _state, _ctx| suuankou(vec_mn),
+    },
+    Yaku {
+        name: "Suuankou tanki",
+        desc: "Four concealed triplets, won on the pair",
+        han: 2,
+        open_score: OpenScore::Illegal,
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| {
+            suuankou(vec_mn)
+                && vec_mn
+                    .iter()
+                    .any(|m| m.pair() && m.win_wait == Some(WinWait::Tanki))
+        },
+    },
+    Yaku {
+        name: "Daisangen",
+        desc: "Big three dragons; triplets/quads of all dragons",
+        han: 1,
+        open_score: OpenScore::Full,
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| vec_mn.iter().filter(|m| m.triplet() && m.dragon()).count() == 3,
+    },
+    Yaku {
+        name: "Shousuushii",
+        desc: "Little four winds; three wind triplets/quads and a wind pair",
+        han: 1,
+        open_score: OpenScore::Full,
+        superseded_by: &["Daisuushii"],
+        f: |vec_mn, _state, _ctx| {
+            vec_mn
+                .iter()
+                .filter(|m| m.triplet() && m.wind().is_some())
+                .count()
+                == 3
+                && vec_mn.iter().any(|m| m.pair() && m.wind().is_some())
+        },
+    },
+    Yaku {
+        name: "Daisuushii",
+        desc: "Big four winds; triplets/quads of all winds",
+        han: 2,
+        open_score: OpenScore::Full,
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| {
+            vec_mn
+                .iter()
+                .filter(|m| m.triplet() && m.wind().is_some())
+                .count()
+                == 4
+        },
+    },
+    Yaku {
+        name: "Ryuuiisou",
+        desc: "All green; only 2, 3, 4, 6, 8 of sou and green dragons",
+        han: 1,
+        open_score: OpenScore::Full,
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| mentsu::get_tiles(vec_mn).iter().all(|t| t.green()),
+    },
+    Yaku {
+        name: "Chuuren poutou",
+        desc: "Nine gates; 1112345678999 of one suit plus any tile of that suit",
+        han: 1,
+        open_score: OpenScore::Illegal,
+        superseded_by: &["Junsei chuuren poutou"],
+        f: |vec_mn, _state, _ctx| chuuren(vec_mn),
+    },
+    Yaku {
+        name: "Junsei chuuren poutou",
+        desc: "True nine gates; 1112345678999 of one suit waiting on all nine tiles",
+        han: 2,
+        open_score: OpenScore::Illegal,
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| {
+            let Some(win_tile) = vec_mn.iter().find_map(|m| m.win_tile) else {
+                return false;
+            };
+
+            let mut tiles = mentsu::get_tiles(vec_mn);
+            if let Some(i) = tiles.iter().position(|&t| t == win_tile) {
+                tiles.remove(i);
+            }
+
+            let count = |v| tiles.iter().filter(|t| t.value == v).count();
+
+            chuuren(vec_mn) && count(1) == 3 && count(9) == 3 && (2..=8).all(|v| count(v) == 1)
+        },
+    },
+    Yaku {
+        name: "Suukantsu",
+        desc: "Four quads",
+        han: 1,
+        open_score: OpenScore::Full,
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| vec_mn.iter().filter(|m| m.quad()).count() == 4,
+    },
+    Yaku {
+        name: "Tenhou",
+        desc: "Dealer wins on the initial deal",
+        han: 1,
+        open_score: OpenScore::Illegal,
+        superseded_by: &[],
+        f: |vec_mn, state, ctx| first_draw(vec_mn, ctx) && state.dealer,
+    },
+    Yaku {
+        name: "Chiihou",
+        desc: "Non-dealer wins by tsumo on their first draw",
+        han: 1,
+        open_score: OpenScore::Illegal,
+        superseded_by: &[],
+        f: |vec_mn, state, ctx| first_draw(vec_mn, ctx) && !state.dealer,
+    },
+    Yaku {
+        name: "Kokushi musou",
+        desc: "13 terminals/honors + a copy of one",
+        han: 1,
+        open_score: OpenScore::Illegal,
+        superseded_by: &["Kokushi musou juusanmen"],
+        f: |vec_mn, _state, _ctx| vec_mn.iter().any(|m| m.single()),
+    },
+    Yaku {
+        name: "Kokushi musou juusanmen",
+        desc: "Thirteen orphans waiting on all thirteen tiles",
+        han: 2,
+        open_score: OpenScore::Illegal,
+        superseded_by: &[],
+        f: |vec_mn, _state, _ctx| {
+            vec_mn.iter().any(|m| m.single())
+                && vec_mn.iter().any(|m| m.pair() && m.win_wait.is_some())
+        },
     },
 ];
+
+fn suuankou(hand: &[Mentsu]) -> bool {
+    hand.iter().filter(|m| m.closed() && m.triplet()).count() >= 4
+}
+
+fn chuuren(hand: &[Mentsu]) -> bool {
+    let tiles = mentsu::get_tiles(hand);
+
+    let Some(first) = tiles.first() else {
+        return false;
+    };
+
+    if first.honor() || tiles.iter().any(|t| t.suit != first.suit) {
+        return false;
+    }
+
+    let count = |v| tiles.iter().filter(|t| t.value == v).count();
+
+    count(1) >= 3 && count(9) >= 3 && (2..=8).all(|v| count(v) >= 1)
+}
+
+/// A self-drawn win on the first turn, before any calls or kans.
+fn first_draw(hand: &[Mentsu], ctx: WinContext) -> bool {
+    ctx.first_turn && mentsu::tsumo(hand) && hand.iter().all(|m| m.call.is_none())
+}